@@ -0,0 +1,122 @@
+//! The letters a puzzle is played with. Bags and packed words refer to letters
+//! by their position in the alphabet, so the search never sees chars.
+
+use crate::error::{Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Most letters an alphabet can hold. The bag has one more slot, for the
+/// wildcard.
+pub const MAX_LETTERS: usize = 63;
+
+/// An ordered set of uppercase letters, A-Z unless the puzzle says otherwise.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Alphabet {
+    letters: [char; MAX_LETTERS],
+    len: usize,
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::latin()
+    }
+}
+
+impl Alphabet {
+    /// A to Z.
+    pub fn latin() -> Self {
+        Alphabet::new('A'..='Z').expect("A-Z is a valid alphabet")
+    }
+
+    /// An alphabet of `letters` in the order given. They have to be uppercase
+    /// letters, with no repeats.
+    pub fn new(letters: impl IntoIterator<Item = char>) -> Result<Self> {
+        Alphabet {
+            letters: ['\0'; MAX_LETTERS],
+            len: 0,
+        }
+        .with(letters)
+    }
+
+    /// This alphabet with any of `letters` it's missing added at the end.
+    pub fn with(mut self, letters: impl IntoIterator<Item = char>) -> Result<Self> {
+        for c in letters {
+            if !(c.is_alphabetic() && c.is_uppercase()) {
+                return Err(Error::Parse(format!(
+                    "{c:?} can't be in an alphabet, only uppercase letters can"
+                )));
+            }
+            if self.contains(c) {
+                continue;
+            }
+            if self.len == MAX_LETTERS {
+                return Err(Error::Parse(format!(
+                    "alphabets can't have more than {MAX_LETTERS} letters"
+                )));
+            }
+            self.letters[self.len] = c;
+            self.len += 1;
+        }
+        Ok(self)
+    }
+
+    pub fn letters(&self) -> &[char] {
+        &self.letters[..self.len]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        self.index_of(c).is_some()
+    }
+
+    /// Position of `c` in the alphabet.
+    pub fn index_of(&self, c: char) -> Option<usize> {
+        self.letters().iter().position(|&l| l == c)
+    }
+
+    /// The letter at `index`, or `*` for the wildcard's slot.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is past the end of the alphabet and isn't the wildcard's.
+    pub fn letter(&self, index: usize) -> char {
+        if index == MAX_LETTERS {
+            return '*';
+        }
+        self.letters()[index]
+    }
+}
+
+impl FromStr for Alphabet {
+    type Err = Error;
+
+    /// The letters in order, e.g. `ABCDEFGHIJKLMNÑOPQRSTUVWXYZ`. Whitespace is
+    /// ignored.
+    fn from_str(s: &str) -> Result<Self> {
+        let letters = s.chars().filter(|c| !c.is_whitespace()).collect::<Vec<_>>();
+        let alphabet = Alphabet::new(letters.iter().copied())?;
+        if alphabet.len() != letters.len() {
+            return Err(Error::Parse(format!("alphabet `{s}` repeats a letter")));
+        }
+        Ok(alphabet)
+    }
+}
+
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.letters().iter().try_for_each(|c| write!(f, "{c}"))
+    }
+}
+
+impl fmt::Debug for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Alphabet({self})")
+    }
+}
@@ -0,0 +1,86 @@
+//! Deliberately simple reference solver: tries every ordering of rows that fits
+//! the bag, and keeps the boards whose bonus path spells a bonus word. Apart from
+//! dropping boards whose bonus path can no longer spell anything, it makes none
+//! of the search's shortcuts, so it's only usable on small word lists, but it's
+//! the oracle the bonus-first search is checked against.
+
+use crate::packed::PackedWord;
+use crate::progress::{Progress, ProgressCounter};
+use crate::puzzle::{LetterBag, Puzzle};
+use crate::solver::{Board, bonus_of, remove_word_from_bag, score_board};
+use rayon::prelude::*;
+
+/// Whether some bonus word agrees with every bonus path cell placed so far.
+fn bonus_still_possible(
+    board: &[&PackedWord],
+    bonus_words: &[&PackedWord],
+    puzzle: &Puzzle,
+) -> bool {
+    bonus_words.iter().any(|bonus_word| {
+        puzzle
+            .bonus_path
+            .iter()
+            .zip(bonus_word.letters())
+            .all(|(&(r, c), &letter)| r >= board.len() || board[r].letters[c] == letter)
+    })
+}
+
+fn validate_sub_board<'a>(
+    valid_words: &[&'a PackedWord],
+    bonus_words: &[&PackedWord],
+    letter_bag: &LetterBag,
+    puzzle: &Puzzle,
+    board: &mut Board<'a>,
+    best: &mut (Board<'a>, u32),
+) {
+    if !bonus_still_possible(board, bonus_words, puzzle) {
+        return;
+    }
+    if board.len() == 5 {
+        let score = score_board(board, bonus_of(board, bonus_words, puzzle), puzzle);
+        if score > best.1 {
+            *best = (board.clone(), score);
+        }
+        return;
+    }
+    for &word in valid_words {
+        if let Some(bag) = remove_word_from_bag(word, letter_bag) {
+            board.push(word);
+            validate_sub_board(valid_words, bonus_words, &bag, puzzle, board, best);
+            board.pop();
+        }
+    }
+}
+
+/// Finds the best board for every choice of first row, in parallel.
+pub fn solve<'a, F>(
+    valid_words: &[&'a PackedWord],
+    bonus_words: &[&PackedWord],
+    puzzle: &Puzzle,
+    on_progress: F,
+) -> Vec<(Board<'a>, u32)>
+where
+    F: Fn(&Progress) + Sync,
+{
+    let letter_bag = puzzle.letter_bag();
+    let counter = ProgressCounter::new(valid_words.len());
+    valid_words
+        .par_iter()
+        .map(|&first| {
+            let mut best = (vec![], 0);
+            if let Some(bag) = remove_word_from_bag(first, &letter_bag) {
+                let mut board = vec![first];
+                validate_sub_board(
+                    valid_words,
+                    bonus_words,
+                    &bag,
+                    puzzle,
+                    &mut board,
+                    &mut best,
+                );
+            }
+            on_progress(&counter.tick());
+            best
+        })
+        .collect()
+}
@@ -0,0 +1,295 @@
+//! Short codes for sharing a whole puzzle definition, for `solve --code`.
+//!
+//! A code is the puzzle packed into bytes and written in Crockford's base32,
+//! which survives chat clients and reading aloud: it's case-insensitive and
+//! has no punctuation. The bytes are
+//!
+//! - a version byte, then a flags byte (id, custom alphabet, packed schema,
+//!   rounding mode and scope),
+//! - the id and the alphabet if the flags say so, each as a length and UTF-8,
+//! - the tile count, then each tile as its alphabet index (63 for the
+//!   wildcard), its count and its score,
+//! - the schema, two cells to a byte when every multiplier fits in 4 bits,
+//! - the bonus path, a byte per cell,
+//! - the common-word multiplier,
+//! - a CRC-16 of everything before it, so a mistyped or cut-off code is caught.
+//!
+//! Numbers past a byte are LEB128 varints.
+
+use crate::alphabet::{Alphabet, MAX_LETTERS};
+use crate::error::{Error, Result};
+use crate::puzzle::{Letter, Multiplier, Puzzle, RoundingMode, RoundingScope};
+
+const VERSION: u8 = 1;
+
+const HAS_ID: u8 = 1;
+const HAS_ALPHABET: u8 = 1 << 1;
+const PACKED_SCHEMA: u8 = 1 << 2;
+const PER_BOARD: u8 = 1 << 3;
+/// Two bits of rounding mode.
+const ROUNDING_SHIFT: u8 = 4;
+
+/// Crockford's base32 digits.
+const DIGITS: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Index of the wildcard in a packed tile.
+const WILDCARD: u8 = MAX_LETTERS as u8;
+
+fn bad(what: impl std::fmt::Display) -> Error {
+    Error::Parse(format!("bad puzzle code: {what}"))
+}
+
+/// CRC-16/CCITT-FALSE.
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc = 0xffff_u16;
+    for &byte in bytes {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn to_base32(bytes: &[u8]) -> String {
+    let mut code = String::new();
+    let (mut buffer, mut bits) = (0_u32, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            code.push(DIGITS[(buffer >> bits) as usize & 31] as char);
+        }
+    }
+    if bits > 0 {
+        code.push(DIGITS[(buffer << (5 - bits)) as usize & 31] as char);
+    }
+    code
+}
+
+/// Reads base32, ignoring case, spaces and dashes, and taking the look-alikes
+/// I, L and O as 1, 1 and 0 the way Crockford's alphabet intends.
+fn from_base32(code: &str) -> Result<Vec<u8>> {
+    let mut bytes = vec![];
+    let (mut buffer, mut bits) = (0_u32, 0);
+    for c in code.chars() {
+        let digit = match c.to_ascii_uppercase() {
+            ' ' | '-' | '\t' | '\n' | '\r' => continue,
+            'I' | 'L' => 1,
+            'O' => 0,
+            c => DIGITS
+                .iter()
+                .position(|&d| d as char == c)
+                .ok_or_else(|| bad(format!("{c:?} isn't a code character")))?
+                as u32,
+        };
+        buffer = (buffer << 5) | digit;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+fn push_varint(bytes: &mut Vec<u8>, mut n: u32) {
+    while n >= 0x80 {
+        bytes.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    bytes.push(n as u8);
+}
+
+fn push_str(bytes: &mut Vec<u8>, s: &str) {
+    push_varint(bytes, s.len() as u32);
+    bytes.extend_from_slice(s.as_bytes());
+}
+
+/// The puzzle's share code.
+pub fn encode(puzzle: &Puzzle) -> String {
+    let custom_alphabet = puzzle.alphabet != Alphabet::latin();
+    let packed_schema = puzzle.schema.iter().flatten().all(|&m| m < 16);
+    let rounding = match puzzle.rounding {
+        RoundingMode::Floor => 0,
+        RoundingMode::Ceil => 1,
+        RoundingMode::Nearest => 2,
+    };
+    let mut flags = rounding << ROUNDING_SHIFT;
+    for (set, flag) in [
+        (puzzle.id.is_some(), HAS_ID),
+        (custom_alphabet, HAS_ALPHABET),
+        (packed_schema, PACKED_SCHEMA),
+        (puzzle.rounding_scope == RoundingScope::PerBoard, PER_BOARD),
+    ] {
+        if set {
+            flags |= flag;
+        }
+    }
+
+    let mut bytes = vec![VERSION, flags];
+    if let Some(id) = &puzzle.id {
+        push_str(&mut bytes, id);
+    }
+    if custom_alphabet {
+        push_str(&mut bytes, &puzzle.alphabet.to_string());
+    }
+    bytes.push(puzzle.letters.len() as u8);
+    for letter in &puzzle.letters {
+        let index = puzzle
+            .letter_index(letter.ch)
+            .expect("tiles are in the puzzle's alphabet");
+        bytes.extend([index as u8, letter.num]);
+        push_varint(&mut bytes, letter.score);
+    }
+    let cells = puzzle.schema.iter().flatten().copied().collect::<Vec<_>>();
+    if packed_schema {
+        bytes.extend(
+            cells
+                .chunks(2)
+                .map(|pair| (pair[0] << 4) as u8 | pair.get(1).copied().unwrap_or(0) as u8),
+        );
+    } else {
+        cells.iter().for_each(|&m| push_varint(&mut bytes, m));
+    }
+    bytes.extend(puzzle.bonus_path.map(|(row, col)| (row * 5 + col) as u8));
+    push_varint(&mut bytes, puzzle.common_multiplier.num);
+    push_varint(&mut bytes, puzzle.common_multiplier.den);
+    bytes.extend(crc16(&bytes).to_be_bytes());
+    to_base32(&bytes)
+}
+
+/// Reads the fields of a code, in order.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8> {
+        let (&first, rest) = self
+            .bytes
+            .split_first()
+            .ok_or_else(|| bad("it's cut short"))?;
+        self.bytes = rest;
+        Ok(first)
+    }
+
+    fn varint(&mut self) -> Result<u32> {
+        let mut n = 0_u32;
+        for shift in (0..32).step_by(7) {
+            let byte = self.byte()?;
+            // The last byte only has room for the top four bits of a u32
+            if shift == 28 && byte & 0x70 != 0 {
+                break;
+            }
+            n |= ((byte & 0x7f) as u32) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err(bad("a number is too large"))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.varint()? as usize;
+        if len > self.bytes.len() {
+            return Err(bad("it's cut short"));
+        }
+        let (s, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        String::from_utf8(s.to_vec()).map_err(|_| bad("a name isn't UTF-8"))
+    }
+}
+
+/// The puzzle a share code was made from.
+pub fn decode(code: &str) -> Result<Puzzle> {
+    let bytes = from_base32(code)?;
+    let Some(body_len) = bytes.len().checked_sub(2).filter(|&len| len > 0) else {
+        return Err(bad("it's too short"));
+    };
+    let (body, checksum) = bytes.split_at(body_len);
+    if body.first().is_some_and(|&v| v != VERSION) {
+        return Err(bad(format!(
+            "it's version {}, but only version {VERSION} is supported",
+            body[0]
+        )));
+    }
+    if crc16(body).to_be_bytes() != checksum {
+        return Err(bad(
+            "the checksum doesn't match; is part of it missing or mistyped?",
+        ));
+    }
+
+    let mut reader = Reader { bytes: &body[1..] };
+    let flags = reader.byte()?;
+    let mut puzzle = Puzzle {
+        rounding: match flags >> ROUNDING_SHIFT {
+            0 => RoundingMode::Floor,
+            1 => RoundingMode::Ceil,
+            2 => RoundingMode::Nearest,
+            n => return Err(bad(format!("unknown rounding {n}"))),
+        },
+        rounding_scope: if flags & PER_BOARD != 0 {
+            RoundingScope::PerBoard
+        } else {
+            RoundingScope::PerWord
+        },
+        ..Puzzle::default()
+    };
+    if flags & HAS_ID != 0 {
+        puzzle.id = Some(reader.string()?);
+    }
+    if flags & HAS_ALPHABET != 0 {
+        puzzle.alphabet = reader.string()?.parse()?;
+    }
+    let tiles = reader.byte()?;
+    puzzle.letters = (0..tiles)
+        .map(|_| {
+            let index = reader.byte()?;
+            let ch = match index {
+                WILDCARD => '*',
+                i if (i as usize) < puzzle.alphabet.len() => puzzle.alphabet.letter(i as usize),
+                i => return Err(bad(format!("letter {i} is past the end of the alphabet"))),
+            };
+            let num = reader.byte()?;
+            let score = reader.varint()?;
+            Ok(Letter { ch, num, score })
+        })
+        .collect::<Result<_>>()?;
+    if flags & PACKED_SCHEMA != 0 {
+        for pair in 0..13 {
+            let byte = reader.byte()? as u32;
+            for (i, m) in [(2 * pair, byte >> 4), (2 * pair + 1, byte & 15)] {
+                if i < 25 {
+                    puzzle.schema[i / 5][i % 5] = m;
+                }
+            }
+        }
+    } else {
+        for i in 0..25 {
+            puzzle.schema[i / 5][i % 5] = reader.varint()?;
+        }
+    }
+    for cell in &mut puzzle.bonus_path {
+        let i = reader.byte()? as usize;
+        if i >= 25 {
+            return Err(bad(format!("bonus cell {i} is off the grid")));
+        }
+        *cell = (i / 5, i % 5);
+    }
+    let num = reader.varint()?;
+    let den = reader.varint()?;
+    if den == 0 {
+        return Err(bad("the multiplier divides by zero"));
+    }
+    puzzle.common_multiplier = Multiplier { num, den };
+    if !reader.bytes.is_empty() {
+        return Err(bad("it has extra data at the end"));
+    }
+    puzzle.check_limits().map_err(bad)?;
+    Ok(puzzle)
+}
@@ -0,0 +1,130 @@
+use super::{Args, LoadFlags, PuzzleFlags, unknown_argument};
+use bongo_game::dictionary::{Dictionary, STDIN_PATH};
+use bongo_game::packed::PackedWord;
+use bongo_game::progress::ProgressBar;
+use bongo_game::puzzle::Puzzle;
+use bongo_game::share::SharedBoard;
+use bongo_game::solver::{Strategy, solve_dictionary};
+use bongo_game::{Error, Result};
+use std::io::Read;
+
+pub const USAGE: &str = "bongo_game check [FILE|-] [-q|--quiet] [--dict PATH] [--strict] \
+                         [--drop-accented] [--alphabet LETTERS] [--feedback PATH|--no-feedback] \
+                         [--puzzle PATH|--code CODE] [--optimum SCORE] \
+                         [--strategy bonus-first|brute-force]";
+
+pub(super) struct Options {
+    pub quiet: bool,
+    /// The shared result, or stdin.
+    pub input: String,
+    pub dict: String,
+    pub load: LoadFlags,
+    pub puzzle: PuzzleFlags,
+    /// The best score, if known; otherwise the puzzle is solved for it.
+    pub optimum: Option<u32>,
+    pub strategy: Strategy,
+}
+
+pub(super) fn parse_args(mut args: Args) -> Result<Options> {
+    let mut options = Options {
+        quiet: false,
+        input: STDIN_PATH.to_string(),
+        dict: "bongo-common-words.txt".to_string(),
+        load: LoadFlags::default(),
+        puzzle: PuzzleFlags::default(),
+        optimum: None,
+        strategy: Strategy::BonusFirst,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-q" | "--quiet" => options.quiet = true,
+            "--dict" => options.dict = args.value(&arg)?,
+            "--optimum" => options.optimum = Some(args.parsed(&arg)?),
+            "--strategy" => options.strategy = args.value(&arg)?.parse()?,
+            _ if options.load.parse(&arg, &mut args)? => {}
+            _ if options.puzzle.parse(&arg, &mut args)? => {}
+            _ if arg == STDIN_PATH || !arg.starts_with('-') => options.input = arg,
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    Ok(options)
+}
+
+pub(super) fn read_input(path: &str) -> Result<String> {
+    if path == STDIN_PATH {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| Error::io("<stdin>", e))?;
+        return Ok(text);
+    }
+    std::fs::read_to_string(path).map_err(|e| Error::io(path, e))
+}
+
+/// The best board and its score, or just the score if `--optimum` gave it.
+pub(super) fn optimum(
+    options: &Options,
+    dictionary: &dyn Dictionary,
+    puzzle: &Puzzle,
+) -> (Vec<PackedWord>, u32) {
+    if let Some(optimum) = options.optimum {
+        return (vec![], optimum);
+    }
+    let progress_bar = ProgressBar::new(options.quiet);
+    let best = solve_dictionary(dictionary, puzzle, options.strategy, |p| {
+        progress_bar.update(p)
+    });
+    progress_bar.finish();
+    best
+}
+
+/// Scores a shared board and compares it with the optimum, returning false if
+/// the score it claims is wrong.
+pub fn run(args: Args) -> Result<bool> {
+    let mut options = parse_args(args)?;
+    let puzzle = options.puzzle.load()?;
+    options.load.use_puzzle_alphabet(&puzzle);
+    let shared = read_input(&options.input)?.parse::<SharedBoard>()?;
+    let (dictionary, summary) = options.load.open(&options.dict)?;
+    print!("{summary}");
+
+    let checked = shared.check(&puzzle, dictionary.as_ref())?;
+    println!();
+    for (row, word) in checked.words.iter().enumerate() {
+        let marked = shared.wildcard.is_some_and(|(r, _)| r == row);
+        println!("{}{}", word.word, if marked { " (wildcard)" } else { "" });
+    }
+    println!(
+        "Bonus word: {} ({})",
+        checked.bonus_word,
+        if checked.bonus_word_used {
+            "scores"
+        } else {
+            "not a word"
+        }
+    );
+    println!("Score: {}", checked.score);
+
+    let mut claim_holds = true;
+    match shared.claimed_score {
+        Some(claimed) if claimed == checked.score => println!("The claimed score is right."),
+        Some(claimed) => {
+            println!("The claimed score {claimed} is wrong.");
+            claim_holds = false;
+        }
+        None => {}
+    }
+
+    let (best, optimum) = optimum(&options, dictionary.as_ref(), &puzzle);
+    if !best.is_empty() {
+        let words = best.iter().map(|w| w.word.as_str()).collect::<Vec<_>>();
+        println!("Best board: {}", words.join(" "));
+    }
+    if optimum > 0 {
+        println!(
+            "Optimum: {optimum} ({:.1}% reached)",
+            100.0 * checked.score as f64 / optimum as f64
+        );
+    }
+    Ok(claim_holds)
+}
@@ -0,0 +1,255 @@
+use super::{Args, LoadFlags, PuzzleFlags, unknown_argument};
+use super::{compiled_format, report_skipped};
+use bongo_game::dictionary::{
+    Capitalized, FstDictionary, IndexDictionary, STDIN_PATH, WordFilter, difference, intersect,
+    load_hunspell, merge,
+};
+use bongo_game::packed::MAX_WORD_LEN;
+use bongo_game::progress::ProgressBar;
+use bongo_game::solver::{Strategy, solve_word_list};
+use bongo_game::{Error, Result};
+use std::collections::HashSet;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub const USAGE: &str = "bongo_game dict merge|intersect|diff|stats LIST...
+       bongo_game dict filter LIST [--length N] [--min-length N] [--max-length N] \
+                         [--pattern B?N*] [--in LIST] [--not-in LIST]
+       bongo_game dict hunspell DIC [AFF] [--puzzle PATH|--code CODE] [--min-length N] [--max-length N] \
+                         [--keep-capitalized]
+       bongo_game dict compile LIST OUT.idx|OUT.fst
+       bongo_game dict report [-q] [--puzzle PATH|--code CODE] [--strategy NAME] LIST...
+       (all dict commands also take [--strict] [--drop-accented] [--alphabet LETTERS]
+       [--feedback PATH] to lay recorded verdicts over the lists, which they
+       otherwise use as they are on disk; lists may be gzip or zstd compressed,
+       and `-` reads one from stdin)";
+
+pub fn run(mut args: Args) -> Result<bool> {
+    let command = args
+        .next()
+        .ok_or_else(|| Error::Parse("dict needs a command".to_string()))?;
+    match command.as_str() {
+        "merge" | "intersect" | "diff" => set_operation(&command, args)?,
+        "filter" => filter(args)?,
+        "stats" => stats(args)?,
+        "report" => report(args)?,
+        "compile" => compile(args)?,
+        "hunspell" => hunspell(args)?,
+        _ => return Err(Error::Parse(format!("unknown dict command `{command}`"))),
+    }
+    Ok(true)
+}
+
+/// Splits the arguments into loading flags and list paths.
+fn parse_lists(mut args: Args) -> Result<(LoadFlags, Vec<String>)> {
+    let mut load = LoadFlags::without_feedback();
+    let mut lists = vec![];
+    while let Some(arg) = args.next() {
+        if load.parse(&arg, &mut args)? {
+            continue;
+        }
+        if is_flag(&arg) {
+            return Err(unknown_argument(&arg));
+        }
+        lists.push(arg);
+    }
+    if lists.is_empty() {
+        return Err(Error::Parse("no word lists given".to_string()));
+    }
+    Ok((load, lists))
+}
+
+/// Whether `arg` is a flag rather than a list, `-` being stdin.
+fn is_flag(arg: &str) -> bool {
+    arg.starts_with('-') && arg != STDIN_PATH
+}
+
+fn print_words(words: &[String]) {
+    let mut out = BufWriter::new(io::stdout().lock());
+    for word in words {
+        // Stop quietly when piped into something like `head`
+        if writeln!(out, "{word}").is_err() {
+            return;
+        }
+    }
+    let _ = out.flush();
+}
+
+fn set_operation(command: &str, args: Args) -> Result<()> {
+    let (load, paths) = parse_lists(args)?;
+    let lists = paths
+        .iter()
+        .map(|path| load.load(path).map(|l| l.words))
+        .collect::<Result<Vec<_>>>()?;
+    let others = lists[1..].iter().map(Vec::as_slice).collect::<Vec<_>>();
+    let words = match command {
+        "merge" => merge(lists.iter().map(Vec::as_slice)),
+        "intersect" => intersect(&lists[0], &others),
+        _ => difference(&lists[0], &others),
+    };
+    print_words(&words);
+    Ok(())
+}
+
+fn filter(mut args: Args) -> Result<()> {
+    let mut load = LoadFlags::without_feedback();
+    let mut word_filter = WordFilter::default();
+    let mut list = None;
+    let mut member_of = None;
+    let mut not_member_of = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--length" => {
+                let len = args.parsed(&arg)?;
+                word_filter.min_len = Some(len);
+                word_filter.max_len = Some(len);
+            }
+            "--min-length" => word_filter.min_len = Some(args.parsed(&arg)?),
+            "--max-length" => word_filter.max_len = Some(args.parsed(&arg)?),
+            "--pattern" => word_filter.pattern = Some(args.value(&arg)?.parse()?),
+            "--in" => member_of = Some(args.value(&arg)?),
+            "--not-in" => not_member_of = Some(args.value(&arg)?),
+            _ if load.parse(&arg, &mut args)? => {}
+            _ if !is_flag(&arg) && list.is_none() => list = Some(arg),
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    let list = list.ok_or_else(|| Error::Parse("no word list given".to_string()))?;
+    let load_set = |path: &str| -> Result<HashSet<String>> {
+        Ok(load.load(path)?.words.into_iter().collect())
+    };
+    if let Some(path) = member_of {
+        word_filter.member_of = Some(load_set(&path)?);
+    }
+    if let Some(path) = not_member_of {
+        word_filter.not_member_of = Some(load_set(&path)?);
+    }
+    print_words(&word_filter.filter(&load.load(&list)?.words));
+    Ok(())
+}
+
+fn stats(args: Args) -> Result<()> {
+    let (load, paths) = parse_lists(args)?;
+    for path in paths {
+        println!("{path}:");
+        print!("{}", load.load(&path)?.stats);
+    }
+    Ok(())
+}
+
+/// Solves the puzzle with each list on its own, and with all of them merged,
+/// to show which list the best score depends on.
+fn report(mut args: Args) -> Result<()> {
+    let mut quiet = false;
+    let mut puzzle = PuzzleFlags::default();
+    let mut strategy = Strategy::BonusFirst;
+    let mut rest = vec![];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-q" | "--quiet" => quiet = true,
+            "--strategy" => strategy = args.value(&arg)?.parse()?,
+            _ if puzzle.parse(&arg, &mut args)? => {}
+            _ => rest.push(arg),
+        }
+    }
+    let puzzle = puzzle.load()?;
+    let (mut load, paths) = parse_lists(Args::new(rest))?;
+    load.use_puzzle_alphabet(&puzzle);
+    let mut lists = paths
+        .iter()
+        .map(|path| Ok((path.clone(), load.load(path)?.words)))
+        .collect::<Result<Vec<_>>>()?;
+    if lists.len() > 1 {
+        let merged = merge(lists.iter().map(|(_, words)| words.as_slice()));
+        lists.push(("(merged)".to_string(), merged));
+    }
+
+    let name_width = lists.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    println!(
+        "{:name_width$}  {:>7}  {:>6}  {:>6}  best board",
+        "list", "words", "score", "change"
+    );
+    let mut first_score = None;
+    for (name, words) in &lists {
+        let progress_bar = ProgressBar::new(quiet);
+        let (board, score) = solve_word_list(words, &puzzle, strategy, |p| progress_bar.update(p));
+        progress_bar.finish();
+        let change = match first_score {
+            None => "-".to_string(),
+            Some(first) => format!("{:+}", score as i64 - first as i64),
+        };
+        first_score.get_or_insert(score);
+        let board = board.iter().map(|w| w.word.as_str()).collect::<Vec<_>>();
+        println!(
+            "{name:name_width$}  {:>7}  {score:>6}  {change:>6}  {}",
+            words.len(),
+            board.join(" ")
+        );
+    }
+    Ok(())
+}
+
+/// Compiles a word list into the binary index or an FST, picked by the output
+/// file's extension.
+fn compile(args: Args) -> Result<()> {
+    let (load, paths) = parse_lists(args)?;
+    let [list, out] = paths.as_slice() else {
+        return Err(Error::Parse(
+            "compile needs a word list and an output path".to_string(),
+        ));
+    };
+    let (dictionary, summary) = load.open(list)?;
+    print!("{summary}");
+    match compiled_format(out) {
+        Some("idx") => IndexDictionary::compile(dictionary.as_ref()).write(out)?,
+        Some("fst") => FstDictionary::compile(dictionary.as_ref()).write(out)?,
+        _ => {
+            return Err(Error::Parse(format!("`{out}` should end in .idx or .fst")));
+        }
+    }
+    println!("Wrote {} words to {out}", dictionary.len());
+    Ok(())
+}
+
+/// Expands a Hunspell dictionary into a word list, by default keeping only the
+/// lengths the puzzle uses: its bonus word up to full rows.
+fn hunspell(mut args: Args) -> Result<()> {
+    let mut load = LoadFlags::without_feedback();
+    let mut puzzle = PuzzleFlags::default();
+    let mut min_len = None;
+    let mut max_len = None;
+    let mut capitalized = Capitalized::Skip;
+    let mut paths = vec![];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--keep-capitalized" => capitalized = Capitalized::Keep,
+            "--min-length" => min_len = Some(args.parsed(&arg)?),
+            "--max-length" => max_len = Some(args.parsed(&arg)?),
+            _ if load.parse(&arg, &mut args)? => {}
+            _ if puzzle.parse(&arg, &mut args)? => {}
+            _ if !is_flag(&arg) => paths.push(arg),
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    let (dic, aff) = match paths.as_slice() {
+        [dic] => (dic.clone(), Path::new(dic).with_extension("aff")),
+        [dic, aff] => (dic.clone(), PathBuf::from(aff)),
+        _ => {
+            return Err(Error::Parse(
+                "hunspell needs a .dic file and optionally its .aff".to_string(),
+            ));
+        }
+    };
+    let puzzle = puzzle.load()?;
+    load.use_puzzle_alphabet(&puzzle);
+    let mut word_list = load_hunspell(&dic, aff, capitalized, load.options())?;
+    report_skipped(&word_list);
+    eprint!("{}", load.apply_feedback(&mut word_list)?);
+    let word_filter = WordFilter {
+        min_len: Some(min_len.unwrap_or(puzzle.bonus_path.len())),
+        max_len: Some(max_len.unwrap_or(MAX_WORD_LEN)),
+        ..WordFilter::default()
+    };
+    print_words(&word_filter.filter(&word_list.words));
+    Ok(())
+}
@@ -0,0 +1,343 @@
+use super::{Args, LoadFlags, PuzzleFlags, unknown_argument};
+use bongo_game::dictionary::Dictionary;
+use bongo_game::editor::{Editor, Grid, RowState};
+use bongo_game::game::{Game, ROWS};
+use bongo_game::packed::{MAX_WORD_LEN, PackedWord};
+use bongo_game::puzzle::Puzzle;
+use bongo_game::solver::{find_valid_dictionary_words, split_valid_words};
+use bongo_game::{Error, Result};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{Scope, ScopedJoinHandle};
+use std::time::Duration;
+
+pub const USAGE: &str = "bongo_game edit [--dict PATH] [--strict] [--drop-accented] \
+                         [--alphabet LETTERS] [--feedback PATH|--no-feedback] \
+                         [--puzzle PATH|--code CODE]";
+
+const KEYS: &str = "arrows move · letters fill · Backspace/Del erase · Enter next row · \
+                    Ctrl-K clear row · Ctrl-Z undo · Ctrl-Y redo · Tab hint · Esc quit";
+
+struct Options {
+    dict: String,
+    load: LoadFlags,
+    puzzle: PuzzleFlags,
+}
+
+fn parse_args(mut args: Args) -> Result<Options> {
+    let mut options = Options {
+        dict: "bongo-common-words.txt".to_string(),
+        load: LoadFlags::default(),
+        puzzle: PuzzleFlags::default(),
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dict" => options.dict = args.value(&arg)?,
+            _ if options.load.parse(&arg, &mut args)? => {}
+            _ if options.puzzle.parse(&arg, &mut args)? => {}
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// The words a hint can use, split as the solver wants them.
+struct Candidates<'a> {
+    rows: Vec<&'a PackedWord>,
+    bonus_words: Vec<&'a PackedWord>,
+}
+
+/// The best board and its score, if there is one.
+type Hint = Option<(Vec<String>, u32)>;
+
+/// A hint being worked out off the event loop, so the editor stays live.
+struct Thinking<'scope> {
+    /// The grid the hint is for.
+    grid: Grid,
+    search: ScopedJoinHandle<'scope, Hint>,
+}
+
+struct App<'a, 'scope> {
+    puzzle: &'a Puzzle,
+    editor: Editor,
+    /// What the last key did, if it's worth saying.
+    status: String,
+    thinking: Option<Thinking<'scope>>,
+    /// Tells a hint still being worked out to stop, on the way out.
+    cancelled: &'scope AtomicBool,
+    quit: bool,
+}
+
+impl<'a, 'scope> App<'a, 'scope> {
+    fn on_key<'env>(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+        game: Game<'env>,
+        words: &'env Candidates,
+        scope: &'scope Scope<'scope, 'env>,
+    ) {
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        self.status.clear();
+        match code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if ctrl => self.quit = true,
+            KeyCode::Char('z') if ctrl && !self.editor.undo() => {
+                self.status = "Nothing to undo.".to_string();
+            }
+            KeyCode::Char('y') if ctrl && !self.editor.redo() => {
+                self.status = "Nothing to redo.".to_string();
+            }
+            KeyCode::Char('k') if ctrl => self.editor.clear_row(self.editor.cursor().0),
+            KeyCode::Tab => self.hint(game, words, scope),
+            KeyCode::Char(c) if !ctrl => {
+                if c != '*'
+                    && self
+                        .puzzle
+                        .alphabet
+                        .index_of(c.to_ascii_uppercase())
+                        .is_some()
+                {
+                    self.editor.type_letter(c);
+                } else {
+                    self.status = format!("{c:?} isn't in the puzzle.");
+                }
+            }
+            KeyCode::Backspace => self.editor.backspace(),
+            KeyCode::Delete => self.editor.delete(),
+            KeyCode::Up => self.editor.move_cursor(-1, 0),
+            KeyCode::Down => self.editor.move_cursor(1, 0),
+            KeyCode::Left => self.editor.move_cursor(0, -1),
+            KeyCode::Right => self.editor.move_cursor(0, 1),
+            KeyCode::Home => self.editor.move_cursor(0, -(MAX_WORD_LEN as isize)),
+            KeyCode::End => self.editor.move_cursor(0, MAX_WORD_LEN as isize),
+            KeyCode::Enter => self.editor.move_cursor(1, -(MAX_WORD_LEN as isize)),
+            _ => {}
+        }
+    }
+
+    /// Starts working out the best completion of the rows placed so far.
+    fn hint<'env>(
+        &mut self,
+        game: Game<'env>,
+        words: &'env Candidates,
+        scope: &'scope Scope<'scope, 'env>,
+    ) {
+        if self.thinking.is_some() {
+            self.status = "Still thinking…".to_string();
+            return;
+        }
+        let cancelled = self.cancelled;
+        let search = scope
+            .spawn(move || game.best_completion_unless(&words.rows, &words.bonus_words, cancelled));
+        self.thinking = Some(Thinking {
+            grid: *self.editor.grid(),
+            search,
+        });
+        self.status = "Thinking…".to_string();
+    }
+
+    /// Fills the board in with a finished hint, unless the board changed
+    /// while it was being worked out.
+    fn finish_hint(&mut self) {
+        let Some(thinking) = self.thinking.take_if(|t| t.search.is_finished()) else {
+            return;
+        };
+        let hint = thinking
+            .search
+            .join()
+            .expect("the hint search doesn't panic");
+        if *self.editor.grid() != thinking.grid {
+            self.status = "The board changed while thinking; press Tab again.".to_string();
+            return;
+        }
+        match hint {
+            Some((board, score)) => {
+                self.editor.fill(&board);
+                self.status = format!("The best board with these rows scores {score}.");
+            }
+            None => {
+                self.status = "No board with these rows spells a bonus word.".to_string();
+            }
+        }
+    }
+}
+
+/// A grid cell: its letter, or its multiplier while it's empty, on a colour
+/// for the bonus path or the multiplier.
+fn cell<'a>(
+    puzzle: &Puzzle,
+    row: usize,
+    col: usize,
+    letter: Option<char>,
+    cursor: bool,
+) -> Span<'a> {
+    let multiplier = puzzle.rules.cell_multiplier(puzzle, row, col);
+    let text = match (letter, multiplier) {
+        (Some(c), _) => format!("  {c}  "),
+        (None, 0 | 1) => "  ·  ".to_string(),
+        (None, m) => format!(" ×{m}  "),
+    };
+    let mut style = Style::default().fg(Color::White);
+    style = if puzzle.bonus_path.contains(&(row, col)) {
+        style.bg(Color::Yellow).fg(Color::Black)
+    } else {
+        match multiplier {
+            0 | 1 => style.bg(Color::DarkGray),
+            2 => style.bg(Color::Blue),
+            _ => style.bg(Color::Magenta),
+        }
+    };
+    if cursor {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
+    Span::styled(text, style.add_modifier(Modifier::BOLD))
+}
+
+fn draw(frame: &mut Frame, app: &App, game: &Game, states: &[RowState; ROWS]) {
+    let puzzle = app.puzzle;
+    let [main, bottom] =
+        Layout::vertical([Constraint::Length(ROWS as u16 * 2 + 3), Constraint::Min(4)])
+            .areas(frame.area());
+    let [grid_area, tiles_area] =
+        Layout::horizontal([Constraint::Min(48), Constraint::Length(22)]).areas(main);
+
+    let scores = game.row_scores();
+    let mut lines = vec![];
+    for row in 0..ROWS {
+        let mut spans = vec![Span::raw(" ")];
+        for col in 0..MAX_WORD_LEN {
+            let letter = app.editor.grid()[row][col];
+            spans.push(cell(
+                puzzle,
+                row,
+                col,
+                letter,
+                app.editor.cursor() == (row, col),
+            ));
+            spans.push(Span::raw(" "));
+        }
+        spans.push(match (&states[row], scores[row]) {
+            (RowState::Placed, Some(score)) => {
+                Span::styled(format!(" {score:>4}"), Style::default().fg(Color::Green))
+            }
+            (RowState::Rejected(why), _) => {
+                Span::styled(format!(" {why}"), Style::default().fg(Color::Red))
+            }
+            _ => Span::raw(""),
+        });
+        lines.push(Line::from(spans));
+        lines.push(Line::raw(""));
+    }
+    let title = format!(" Bongo {} ", puzzle.id.as_deref().unwrap_or("puzzle"));
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(title)),
+        grid_area,
+    );
+
+    let tiles = app
+        .editor
+        .tiles_left(puzzle)
+        .into_iter()
+        .map(|(c, left)| {
+            let style = match left {
+                ..0 => Style::default().fg(Color::Red),
+                0 => Style::default().fg(Color::DarkGray),
+                _ => Style::default(),
+            };
+            let score = puzzle
+                .letters
+                .iter()
+                .find(|l| l.ch == c)
+                .map_or(0, |l| l.score);
+            Line::styled(format!(" {c} ×{left:<3} {score:>4}"), style)
+        })
+        .collect::<Vec<_>>();
+    frame.render_widget(
+        Paragraph::new(tiles).block(Block::bordered().title(" Tiles left ")),
+        tiles_area,
+    );
+
+    let bonus = match game.bonus_word() {
+        Some(word) if game.bonus_word_used() => format!("Bonus word {word} scores"),
+        Some(word) => format!("Bonus word {word} isn't a word"),
+        None => "Bonus word not spelled yet".to_string(),
+    };
+    let summary = vec![
+        Line::raw(format!(" {bonus} · Score {}", game.score())),
+        Line::styled(format!(" {}", app.status), Style::default().fg(Color::Cyan)),
+        Line::styled(format!(" {KEYS}"), Style::default().fg(Color::DarkGray)),
+    ];
+    frame.render_widget(Paragraph::new(summary).block(Block::bordered()), bottom);
+}
+
+/// How often the screen is refreshed while a hint is being worked out.
+const THINKING_POLL: Duration = Duration::from_millis(100);
+
+fn event_loop<'env, 'scope>(
+    terminal: &mut DefaultTerminal,
+    app: &mut App<'env, 'scope>,
+    dictionary: &'env dyn Dictionary,
+    words: &'env Candidates,
+    scope: &'scope Scope<'scope, 'env>,
+) -> std::io::Result<()> {
+    while !app.quit {
+        app.finish_hint();
+        let (game, states) = app.editor.game(app.puzzle, dictionary);
+        terminal.draw(|frame| draw(frame, app, &game, &states))?;
+        // Keep checking on a hint being worked out, even with no keys pressed
+        if app.thinking.is_some() && !event::poll(THINKING_POLL)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            app.on_key(key.code, key.modifiers, game, words, scope);
+        }
+    }
+    Ok(())
+}
+
+/// Edits a board full screen, then prints it.
+pub fn run(args: Args) -> Result<bool> {
+    let mut options = parse_args(args)?;
+    let mut puzzle = options.puzzle.load()?;
+    puzzle.id = Some(options.puzzle.puzzle_id(&puzzle));
+    options.load.use_puzzle_alphabet(&puzzle);
+    let (dictionary, _) = options.load.open(&options.dict)?;
+
+    let valid_words = find_valid_dictionary_words(dictionary.as_ref(), &puzzle);
+    let (bonus_words, rows) = split_valid_words(&valid_words, &puzzle);
+    let words = Candidates { rows, bonus_words };
+
+    let cancelled = AtomicBool::new(false);
+    let mut terminal = ratatui::init();
+    let (result, editor) = std::thread::scope(|scope| {
+        let mut app = App {
+            puzzle: &puzzle,
+            editor: Editor::new(),
+            status: String::new(),
+            thinking: None,
+            cancelled: &cancelled,
+            quit: false,
+        };
+        let result = event_loop(&mut terminal, &mut app, dictionary.as_ref(), &words, scope);
+        // Don't wait for a hint nobody will see
+        cancelled.store(true, Ordering::Relaxed);
+        (result, app.editor)
+    });
+    ratatui::restore();
+    result.map_err(|e| Error::io("<terminal>", e))?;
+
+    let (game, _) = editor.game(&puzzle, dictionary.as_ref());
+    for row in 0..ROWS {
+        println!("{}", game.word(row).unwrap_or("....."));
+    }
+    println!("Score: {}", game.score());
+    Ok(true)
+}
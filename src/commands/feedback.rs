@@ -0,0 +1,43 @@
+use super::{Args, unknown_argument};
+use bongo_game::alphabet::Alphabet;
+use bongo_game::feedback::{DEFAULT_FEEDBACK_FILE, FeedbackStore, Verdict};
+use bongo_game::{Error, Result};
+
+pub const USAGE: &str = "bongo_game feedback accepted|rejected|common WORD... [--file PATH] \
+                         [--alphabet LETTERS]
+       bongo_game feedback list [--file PATH]";
+
+pub fn run(mut args: Args) -> Result<bool> {
+    let command = args
+        .next()
+        .ok_or_else(|| Error::Parse("feedback needs a command".to_string()))?;
+    let mut file = DEFAULT_FEEDBACK_FILE.to_string();
+    let mut alphabet = Alphabet::default();
+    let mut words = vec![];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--file" => file = args.value(&arg)?,
+            "--alphabet" => alphabet = args.value(&arg)?.parse()?,
+            _ if arg.starts_with('-') => return Err(unknown_argument(&arg)),
+            _ => words.push(arg),
+        }
+    }
+    let mut store = FeedbackStore::load(&file)?;
+    if command == "list" {
+        if !words.is_empty() {
+            return Err(unknown_argument(&words[0]));
+        }
+        for (word, verdict) in store.verdicts() {
+            println!("{verdict} {word}");
+        }
+        return Ok(true);
+    }
+    let verdict = command.parse::<Verdict>()?;
+    if words.is_empty() {
+        return Err(Error::Parse("no words given".to_string()));
+    }
+    for word in &words {
+        store.record(word, verdict, &alphabet)?;
+    }
+    Ok(true)
+}
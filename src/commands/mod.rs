@@ -0,0 +1,278 @@
+pub mod check;
+pub mod dict;
+pub mod edit;
+pub mod feedback;
+pub mod play;
+pub mod puzzle;
+pub mod share;
+pub mod solve;
+pub mod stats;
+pub mod team;
+
+use bongo_game::alphabet::Alphabet;
+use bongo_game::dictionary::{
+    Diacritics, Dictionary, FstDictionary, IndexDictionary, LoadOptions, Strictness,
+    TextDictionary, load_words,
+};
+use bongo_game::feedback::{DEFAULT_FEEDBACK_FILE, FeedbackDictionary, FeedbackStore, Overlay};
+use bongo_game::puzzle::Puzzle;
+use bongo_game::{Error, Result};
+use std::path::Path;
+
+/// How many skipped dictionary lines to list before just counting them.
+const MAX_REPORTED_LINES: usize = 10;
+
+/// The remaining command line arguments.
+pub struct Args(std::vec::IntoIter<String>);
+
+impl Args {
+    pub fn new(args: Vec<String>) -> Self {
+        Args(args.into_iter())
+    }
+
+    pub fn next(&mut self) -> Option<String> {
+        self.0.next()
+    }
+
+    /// The value following `flag`.
+    pub fn value(&mut self, flag: &str) -> Result<String> {
+        self.0
+            .next()
+            .ok_or_else(|| Error::Parse(format!("{flag} needs a value")))
+    }
+
+    pub fn parsed<T: std::str::FromStr>(&mut self, flag: &str) -> Result<T> {
+        let value = self.value(flag)?;
+        value
+            .parse()
+            .map_err(|_| Error::Parse(format!("bad value `{value}` for {flag}")))
+    }
+}
+
+pub fn unknown_argument(arg: &str) -> Error {
+    Error::Parse(format!("unknown argument `{arg}`"))
+}
+
+/// Dictionary loading flags shared by every command that reads word lists.
+pub struct LoadFlags {
+    /// Fail on bad dictionary lines instead of skipping them.
+    strict: bool,
+    /// Drop accented words instead of folding them into the alphabet.
+    drop_accented: bool,
+    /// Letters words are made of, A-Z unless a puzzle or `--alphabet` says so.
+    alphabet: Option<Alphabet>,
+    /// Verdicts laid over the dictionary, unless `--no-feedback`.
+    feedback: Option<String>,
+}
+
+impl Default for LoadFlags {
+    fn default() -> Self {
+        LoadFlags {
+            strict: false,
+            drop_accented: false,
+            alphabet: None,
+            feedback: Some(DEFAULT_FEEDBACK_FILE.to_string()),
+        }
+    }
+}
+
+/// A line saying what the feedback from `path` changed, or nothing if it
+/// changed nothing.
+fn feedback_note(path: &Path, overlay: Overlay) -> String {
+    if overlay == Overlay::default() {
+        return String::new();
+    }
+    format!(
+        "Feedback from {}: {} words added, {} removed\n",
+        path.display(),
+        overlay.added,
+        overlay.removed
+    )
+}
+
+impl LoadFlags {
+    /// Flags for commands that work on word lists as they are on disk, where
+    /// feedback is only laid over them if `--feedback` asks for it.
+    pub fn without_feedback() -> Self {
+        LoadFlags {
+            feedback: None,
+            ..LoadFlags::default()
+        }
+    }
+
+    /// Takes `arg`, and its value from `args`, if it's one of the loading flags.
+    pub fn parse(&mut self, arg: &str, args: &mut Args) -> Result<bool> {
+        match arg {
+            "--strict" => self.strict = true,
+            "--drop-accented" => self.drop_accented = true,
+            "--alphabet" => self.alphabet = Some(args.value(arg)?.parse()?),
+            "--feedback" => self.feedback = Some(args.value(arg)?),
+            "--no-feedback" => self.feedback = None,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Reads words in the puzzle's alphabet, unless `--alphabet` gave another.
+    pub fn use_puzzle_alphabet(&mut self, puzzle: &Puzzle) {
+        self.alphabet.get_or_insert(puzzle.alphabet);
+    }
+
+    pub fn options(&self) -> LoadOptions {
+        LoadOptions {
+            strictness: if self.strict {
+                Strictness::Reject
+            } else {
+                Strictness::Skip
+            },
+            diacritics: if self.drop_accented {
+                Diacritics::Drop
+            } else {
+                Diacritics::Fold
+            },
+            alphabet: self.alphabet.unwrap_or_default(),
+        }
+    }
+
+    fn feedback(&self) -> Result<Option<FeedbackStore>> {
+        self.feedback.as_ref().map(FeedbackStore::load).transpose()
+    }
+
+    /// Loads a word list with the feedback laid over it, reporting skipped
+    /// lines and what the feedback changed on stderr.
+    pub fn load(&self, path: &str) -> Result<bongo_game::dictionary::WordList> {
+        let (word_list, note) = self.load_with_note(path)?;
+        eprint!("{note}");
+        Ok(word_list)
+    }
+
+    fn load_with_note(&self, path: &str) -> Result<(bongo_game::dictionary::WordList, String)> {
+        let mut word_list = load_words(path, self.options())?;
+        report_skipped(&word_list);
+        let note = self.apply_feedback(&mut word_list)?;
+        Ok((word_list, note))
+    }
+
+    /// Lays the feedback over `word_list`, returning a line saying what it
+    /// changed.
+    pub fn apply_feedback(
+        &self,
+        word_list: &mut bongo_game::dictionary::WordList,
+    ) -> Result<String> {
+        Ok(match self.feedback()? {
+            Some(store) => feedback_note(store.path(), store.apply(&mut word_list.words)),
+            None => String::new(),
+        })
+    }
+
+    /// Opens a dictionary in whichever format its extension says: `.idx` for
+    /// the compiled index, `.fst` for an FST, and plain text otherwise, with
+    /// the feedback laid over it. Also returns a summary of what was loaded,
+    /// for the caller to print.
+    pub fn open(&self, path: &str) -> Result<(Box<dyn Dictionary>, String)> {
+        let (dictionary, mut summary): (Box<dyn Dictionary>, String) = match compiled_format(path) {
+            Some(format) => {
+                let dictionary: Box<dyn Dictionary> = match format {
+                    "idx" => Box::new(IndexDictionary::open(path)?),
+                    _ => Box::new(FstDictionary::open(path)?),
+                };
+                let summary = format!("Compiled dictionary with {} words\n", dictionary.len());
+                (dictionary, summary)
+            }
+            None => {
+                let word_list = load_words(path, self.options())?;
+                report_skipped(&word_list);
+                let summary = word_list.stats.to_string();
+                (Box::new(TextDictionary::from_word_list(word_list)), summary)
+            }
+        };
+        let Some(store) = self.feedback()? else {
+            return Ok((dictionary, summary));
+        };
+        let path = store.path().to_path_buf();
+        let dictionary = FeedbackDictionary::new(dictionary, store);
+        summary.push_str(&feedback_note(&path, dictionary.overlay()));
+        Ok((Box::new(dictionary), summary))
+    }
+}
+
+/// Lists the first few skipped lines of `word_list` on stderr.
+pub fn report_skipped(word_list: &bongo_game::dictionary::WordList) {
+    for bad_line in word_list.skipped.iter().take(MAX_REPORTED_LINES) {
+        eprintln!("skipped {bad_line}");
+    }
+    if word_list.skipped.len() > MAX_REPORTED_LINES {
+        eprintln!(
+            "... and {} more skipped lines",
+            word_list.skipped.len() - MAX_REPORTED_LINES
+        );
+    }
+}
+
+/// `idx` or `fst` if `path` names a compiled dictionary.
+pub fn compiled_format(path: &str) -> Option<&str> {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .filter(|e| matches!(*e, "idx" | "fst"))
+}
+
+/// Where a command's puzzle comes from, shared by every command that takes one.
+#[derive(Default)]
+pub struct PuzzleFlags {
+    pub path: Option<String>,
+    /// A puzzle share code, instead of a puzzle file.
+    pub code: Option<String>,
+}
+
+impl PuzzleFlags {
+    /// Takes `arg`, and its value from `args`, if it's `--puzzle` or `--code`.
+    pub fn parse(&mut self, arg: &str, args: &mut Args) -> Result<bool> {
+        match arg {
+            "--puzzle" => self.path = Some(args.value(arg)?),
+            "--code" => self.code = Some(args.value(arg)?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// The puzzle from the file or the code, or the built-in one.
+    pub fn load(&self) -> Result<Puzzle> {
+        match (&self.path, &self.code) {
+            (Some(_), Some(_)) => Err(Error::Parse(
+                "give either --puzzle or --code, not both".to_string(),
+            )),
+            (_, Some(code)) => bongo_game::code::decode(code),
+            (path, None) => load_puzzle(path.as_deref()),
+        }
+    }
+
+    /// The puzzle's own id, or else its file name, or `default` for the
+    /// built-in one.
+    pub fn puzzle_id(&self, puzzle: &Puzzle) -> String {
+        puzzle
+            .id
+            .clone()
+            .or_else(|| {
+                let stem = Path::new(self.path.as_ref()?).file_stem()?;
+                Some(stem.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "default".to_string())
+    }
+}
+
+/// `seconds` as minutes and seconds, like `2:05`.
+pub fn format_seconds(seconds: u64) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Reads the puzzle at `path`, or the built-in one.
+fn load_puzzle(path: Option<&str>) -> Result<Puzzle> {
+    let Some(path) = path else {
+        // Hardcoded puzzle
+        return Ok(Puzzle::default());
+    };
+    let text = std::fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+    text.parse()
+        .map_err(|e| Error::Parse(format!("{path}: {e}")))
+}
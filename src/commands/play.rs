@@ -0,0 +1,268 @@
+use super::{Args, LoadFlags, PuzzleFlags, format_seconds, unknown_argument};
+use bongo_game::code::encode;
+use bongo_game::game::{Game, ROWS};
+use bongo_game::history::{self, Entry};
+use bongo_game::progress::ProgressBar;
+use bongo_game::puzzle::Puzzle;
+use bongo_game::solver::{Strategy, solve_dictionary};
+use bongo_game::{Error, Result};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+pub const USAGE: &str = "bongo_game play [-q|--quiet] [--dict PATH] [--strict] [--drop-accented] \
+                         [--alphabet LETTERS] [--feedback PATH|--no-feedback] \
+                         [--puzzle PATH|--code CODE] [--strategy bonus-first|brute-force] \
+                         [--time SECONDS] [--history PATH]";
+
+const HELP: &str = "Commands:
+  WORD          put WORD in the first empty row
+  N WORD        put WORD in row N, replacing what's there
+  clear N       take the word out of row N
+  done          finish and see the best board
+  quit          stop without finishing
+  help          show this";
+
+struct Options {
+    quiet: bool,
+    dict: String,
+    load: LoadFlags,
+    puzzle: PuzzleFlags,
+    /// Finds the optimum revealed at the end.
+    strategy: Strategy,
+    /// The time limit, after which the board is taken as it is.
+    time: Option<u64>,
+    /// Where finished games are recorded.
+    history: PathBuf,
+}
+
+fn parse_args(mut args: Args) -> Result<Options> {
+    let mut options = Options {
+        quiet: false,
+        dict: "bongo-common-words.txt".to_string(),
+        load: LoadFlags::default(),
+        puzzle: PuzzleFlags::default(),
+        strategy: Strategy::BonusFirst,
+        time: None,
+        history: PathBuf::from(history::DEFAULT_PATH),
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-q" | "--quiet" => options.quiet = true,
+            "--dict" => options.dict = args.value(&arg)?,
+            "--strategy" => options.strategy = args.value(&arg)?.parse()?,
+            "--time" => options.time = Some(args.parsed(&arg)?),
+            "--history" => options.history = args.value(&arg)?.into(),
+            _ if options.load.parse(&arg, &mut args)? => {}
+            _ if options.puzzle.parse(&arg, &mut args)? => {}
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// The tiles left, with their scores.
+fn format_bag(game: &Game) -> String {
+    let remaining = game.remaining();
+    game.puzzle
+        .letters
+        .iter()
+        .filter_map(|l| {
+            let count = remaining[game.puzzle.letter_index(l.ch)?];
+            Some(format!("{}×{count} ({})", l.ch, l.score))
+        })
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// One grid cell: the letter or a dot, the cell's multiplier if it has one,
+/// and brackets on the bonus path.
+fn format_cell(puzzle: &Puzzle, row: usize, col: usize, letter: Option<char>) -> String {
+    let multiplier = puzzle.rules.cell_multiplier(puzzle, row, col);
+    let inner = match (letter, multiplier) {
+        (None, 0 | 1) => " · ".to_string(),
+        (None, m) => format!("×{m} "),
+        (Some(c), 0 | 1) => format!(" {c} "),
+        (Some(c), m) => format!("{c}×{m}"),
+    };
+    if puzzle.bonus_path.contains(&(row, col)) {
+        format!("[{inner}]")
+    } else {
+        format!(" {inner} ")
+    }
+}
+
+/// The grid with each row's word and score.
+fn format_board(game: &Game) -> String {
+    let scores = game.row_scores();
+    let mut lines = vec!["      1    2    3    4    5".to_string()];
+    for (row, score) in scores.iter().enumerate() {
+        let word = game.word(row);
+        let cells = (0..5)
+            .map(|col| format_cell(game.puzzle, row, col, word.and_then(|w| w.chars().nth(col))))
+            .collect::<String>();
+        let score = score.map_or_else(String::new, |s| format!("  {s}"));
+        lines.push(format!("{}  {cells}{score}", row + 1));
+    }
+    lines.join("\n")
+}
+
+fn show(game: &Game) {
+    println!("\n{}", format_board(game));
+    if let Some(bonus) = game.bonus_word() {
+        let verdict = if game.bonus_word_used() {
+            "scores"
+        } else {
+            "not a word"
+        };
+        println!("Bonus word: {bonus} ({verdict})");
+    }
+    println!("Score: {}", game.score());
+    println!("Tiles: {}", format_bag(game));
+}
+
+fn prompt(time_left: Option<Duration>) {
+    if let Some(left) = time_left {
+        print!("[{}] ", format_seconds(left.as_secs()));
+    }
+    print!("> ");
+    let _ = std::io::stdout().flush();
+}
+
+/// Applies one line of input, returning false once the game is over.
+fn handle(game: &mut Game, line: &str) -> Result<bool> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let row = |n: &str| {
+        n.parse::<usize>()
+            .ok()
+            .filter(|n| (1..=ROWS).contains(n))
+            .map(|n| n - 1)
+            .ok_or_else(|| Error::Parse(format!("rows go from 1 to {ROWS}, not `{n}`")))
+    };
+    match words.as_slice() {
+        [] => {}
+        ["help" | "?"] => println!("{HELP}"),
+        ["done" | "quit"] => return Ok(false),
+        ["clear", n] => {
+            if game.clear(row(n)?).is_none() {
+                println!("Row {n} is already empty.");
+            }
+        }
+        [n, word] => game.place(row(n)?, word)?,
+        [word] => {
+            let row = game
+                .next_empty_row()
+                .ok_or_else(|| Error::Parse("every row is full; use `N WORD`".to_string()))?;
+            game.place(row, word)?;
+        }
+        _ => return Err(Error::Parse(format!("didn't understand `{line}`"))),
+    }
+    Ok(true)
+}
+
+/// Lines from stdin, read on their own thread so the game can stop waiting
+/// for one when time runs out.
+fn stdin_lines() -> mpsc::Receiver<std::io::Result<String>> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+/// Plays the puzzle on stdin, then reveals the best board and records the
+/// game in the history.
+pub fn run(args: Args) -> Result<bool> {
+    let mut options = parse_args(args)?;
+    let puzzle = options.puzzle.load()?;
+    options.load.use_puzzle_alphabet(&puzzle);
+    let (dictionary, summary) = options.load.open(&options.dict)?;
+    print!("{summary}");
+
+    let mut game = Game::new(&puzzle, dictionary.as_ref());
+    println!("\n{HELP}\n\n[ ] is the bonus path and ×N a letter multiplier.");
+    if let Some(seconds) = options.time {
+        println!("You have {}.", format_seconds(seconds));
+    }
+    show(&game);
+    let start = Instant::now();
+    let deadline = options.time.map(|s| start + Duration::from_secs(s));
+    let time_left = || deadline.map(|d| d.saturating_duration_since(Instant::now()));
+    prompt(time_left());
+    let lines = stdin_lines();
+    let mut quit = false;
+    loop {
+        let line = match time_left() {
+            Some(left) => match lines.recv_timeout(left) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    println!("\nTime's up!");
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+            None => match lines.recv() {
+                Ok(line) => line,
+                Err(_) => break,
+            },
+        };
+        let line = line.map_err(|e| Error::io("<stdin>", e))?;
+        match handle(&mut game, &line) {
+            Ok(true) => show(&game),
+            Ok(false) => {
+                quit = line.trim() == "quit";
+                break;
+            }
+            Err(e) => println!("{e}"),
+        }
+        prompt(time_left());
+    }
+    if quit {
+        return Ok(true);
+    }
+    let elapsed = start.elapsed().as_secs();
+    let seconds = options.time.map_or(elapsed, |limit| elapsed.min(limit));
+
+    println!(
+        "\nFinal score: {} in {}",
+        game.score(),
+        format_seconds(seconds)
+    );
+    let progress_bar = ProgressBar::new(options.quiet);
+    let (best, optimum) = solve_dictionary(dictionary.as_ref(), &puzzle, options.strategy, |p| {
+        progress_bar.update(p)
+    });
+    progress_bar.finish();
+    if optimum > 0 {
+        let words = best.iter().map(|w| w.word.as_str()).collect::<Vec<_>>();
+        println!("Best board: {} for {optimum}", words.join(" "));
+        println!(
+            "You reached {:.1}% of the optimum.",
+            100.0 * game.score() as f64 / optimum as f64
+        );
+    }
+
+    let entry = Entry {
+        date: history::today(),
+        code: encode(&puzzle),
+        board: (0..ROWS)
+            .map(|row| game.word(row).unwrap_or_default().to_string())
+            .collect(),
+        score: game.score(),
+        seconds,
+        optimum,
+    };
+    let previous = history::load(&options.history)?;
+    match history::personal_best(&previous, &entry.code) {
+        Some(best) if entry.score > best => println!("A new personal best, up from {best}!"),
+        Some(best) => println!("Your best on this puzzle is {best}."),
+        None => {}
+    }
+    history::append(&options.history, &entry)?;
+    Ok(true)
+}
@@ -0,0 +1,35 @@
+use super::{Args, PuzzleFlags, unknown_argument};
+use bongo_game::code;
+use bongo_game::{Error, Result};
+
+pub const USAGE: &str = "bongo_game puzzle encode [--puzzle PATH|--code CODE]
+       bongo_game puzzle decode CODE";
+
+pub fn run(mut args: Args) -> Result<bool> {
+    let command = args
+        .next()
+        .ok_or_else(|| Error::Parse("puzzle needs a command".to_string()))?;
+    match command.as_str() {
+        "encode" => {
+            let mut puzzle = PuzzleFlags::default();
+            while let Some(arg) = args.next() {
+                if !puzzle.parse(&arg, &mut args)? {
+                    return Err(unknown_argument(&arg));
+                }
+            }
+            // A code comes back out in its canonical form
+            println!("{}", code::encode(&puzzle.load()?));
+        }
+        "decode" => {
+            let code = args
+                .next()
+                .ok_or_else(|| Error::Parse("decode needs a code".to_string()))?;
+            if let Some(arg) = args.next() {
+                return Err(unknown_argument(&arg));
+            }
+            print!("{}", code::decode(&code)?);
+        }
+        _ => return Err(Error::Parse(format!("unknown puzzle command `{command}`"))),
+    }
+    Ok(true)
+}
@@ -0,0 +1,23 @@
+use super::Args;
+use super::check::{optimum, parse_args, read_input};
+use bongo_game::Result;
+use bongo_game::share::SharedBoard;
+
+pub const USAGE: &str = "bongo_game share [FILE|-] [-q|--quiet] [--dict PATH] [--strict] \
+                         [--drop-accented] [--alphabet LETTERS] [--feedback PATH|--no-feedback] \
+                         [--puzzle PATH|--code CODE] [--optimum SCORE] \
+                         [--strategy bonus-first|brute-force]";
+
+/// Prints a spoiler-free summary of a board, to post instead of the board.
+pub fn run(args: Args) -> Result<bool> {
+    let mut options = parse_args(args)?;
+    let mut puzzle = options.puzzle.load()?;
+    puzzle.id = Some(options.puzzle.puzzle_id(&puzzle));
+    options.load.use_puzzle_alphabet(&puzzle);
+    let shared = read_input(&options.input)?.parse::<SharedBoard>()?;
+    let (dictionary, _) = options.load.open(&options.dict)?;
+    let checked = shared.check(&puzzle, dictionary.as_ref())?;
+    let (_, optimum) = optimum(&options, dictionary.as_ref(), &puzzle);
+    print!("{}", checked.summary(&puzzle, optimum));
+    Ok(true)
+}
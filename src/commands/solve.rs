@@ -0,0 +1,239 @@
+use super::{Args, LoadFlags, PuzzleFlags, unknown_argument};
+use bongo_game::html::html_report;
+use bongo_game::packed::PackedWord;
+use bongo_game::progress::ProgressBar;
+use bongo_game::render::board_svg;
+use bongo_game::report::{SolveReport, SolveStats};
+use bongo_game::solver::{
+    Strategy, best_of, bonus_of, find_valid_dictionary_words, split_valid_words, top_boards,
+};
+use bongo_game::{Error, Result};
+use std::str::FromStr;
+use std::time::Instant;
+
+pub const USAGE: &str = "bongo_game [solve] [-q|--quiet] [-v|--verbose] [--dict PATH] [--strict] [--drop-accented] \
+                         [--alphabet LETTERS] \
+                         [--feedback PATH|--no-feedback] \
+                         [--puzzle PATH|--code CODE] [--strategy bonus-first|brute-force] [--verify] \
+                         [--format text|json|jsonl|csv] [--top K] [--svg PATH] [--html PATH]";
+
+/// What the results are printed as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    /// For people: the best board, and with `--verbose` the candidates and
+    /// the first few sets too.
+    Text,
+    /// One JSON document with the top boards.
+    Json,
+    /// One JSON object per board.
+    JsonLines,
+    /// One row per board.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "jsonl" => Ok(Format::JsonLines),
+            "csv" => Ok(Format::Csv),
+            _ => Err(Error::Parse(format!(
+                "unknown format `{s}`, expected `text`, `json`, `jsonl` or `csv`"
+            ))),
+        }
+    }
+}
+
+struct Options {
+    quiet: bool,
+    /// Also dump the row candidates and the first few sets in text output.
+    verbose: bool,
+    dict: String,
+    load: LoadFlags,
+    puzzle: PuzzleFlags,
+    strategy: Strategy,
+    /// Also run the brute-force solver and check it finds the same best score.
+    verify: bool,
+    format: Format,
+    /// How many boards the structured formats and the HTML report include.
+    top: usize,
+    /// Where to draw the best board, if anywhere.
+    svg: Option<String>,
+    /// Where to write the HTML solve report, with the `--top` boards.
+    html: Option<String>,
+}
+
+fn parse_args(mut args: Args) -> Result<Options> {
+    let mut options = Options {
+        quiet: false,
+        verbose: false,
+        dict: "bongo-common-words.txt".to_string(),
+        load: LoadFlags::default(),
+        puzzle: PuzzleFlags::default(),
+        strategy: Strategy::BonusFirst,
+        verify: false,
+        format: Format::Text,
+        top: 1,
+        svg: None,
+        html: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-q" | "--quiet" => options.quiet = true,
+            "-v" | "--verbose" => options.verbose = true,
+            "--dict" => options.dict = args.value(&arg)?,
+            "--strategy" => options.strategy = args.value(&arg)?.parse()?,
+            "--verify" => options.verify = true,
+            "--svg" => options.svg = Some(args.value(&arg)?),
+            "--html" => options.html = Some(args.value(&arg)?),
+            "--format" => options.format = args.value(&arg)?.parse()?,
+            "--top" => {
+                let value = args.value(&arg)?;
+                options.top = value.parse().ok().filter(|&k| k > 0).ok_or_else(|| {
+                    Error::Parse(format!("--top needs a positive number, not `{value}`"))
+                })?;
+            }
+            _ if options.load.parse(&arg, &mut args)? => {}
+            _ if options.puzzle.parse(&arg, &mut args)? => {}
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// Solves the puzzle, returning false if `--verify` found a mismatch.
+pub fn run(args: Args) -> Result<bool> {
+    let mut options = parse_args(args)?;
+    let puzzle = options.puzzle.load()?;
+    options.load.use_puzzle_alphabet(&puzzle);
+    // Structured output keeps stdout for the data
+    let text = options.format == Format::Text;
+    let note = |message: &str| {
+        if text {
+            println!("{message}");
+        } else {
+            eprintln!("{message}");
+        }
+    };
+
+    // Read words from file
+    let (dictionary, summary) = options.load.open(&options.dict)?;
+    note(summary.trim_end());
+    // Generate all possible valid rows
+    let all_valid_words = find_valid_dictionary_words(dictionary.as_ref(), &puzzle);
+
+    let (bonus_words, valid_words) = split_valid_words(&all_valid_words, &puzzle);
+    note(&format!("Number of bonus words: {}", bonus_words.len()));
+    note(&format!("Number of 5 words: {}", valid_words.len()));
+
+    if text && options.verbose {
+        println!(
+            "{:?}",
+            valid_words.iter().map(|w| &w.word).collect::<Vec<_>>()
+        );
+    }
+
+    let progress_bar = ProgressBar::new(options.quiet);
+    let started = Instant::now();
+    let scored_sets = options
+        .strategy
+        .solve(&valid_words, &bonus_words, &puzzle, |p| {
+            progress_bar.update(p)
+        });
+    let elapsed = started.elapsed();
+    progress_bar.finish();
+
+    let stats = SolveStats {
+        strategy: options.strategy.to_string(),
+        dictionary_words: all_valid_words.len(),
+        bonus_candidates: bonus_words.len(),
+        row_candidates: valid_words.len(),
+        boards_found: scored_sets.len(),
+        elapsed_ms: elapsed.as_millis() as u64,
+    };
+    let boards = top_boards(scored_sets.clone(), options.top);
+    let id = options.puzzle.puzzle_id(&puzzle);
+    let report = SolveReport::new(id, stats, &boards, &bonus_words, &puzzle);
+
+    let best = if text {
+        if options.verbose {
+            // Print the first 5 sets
+            for (i, set) in scored_sets.iter().take(5).enumerate() {
+                println!("Set {} with score {}: {}", i, set.1, board_words(&set.0));
+            }
+        }
+
+        let best = best_of(scored_sets);
+        println!();
+        if best.1 > 0 {
+            println!("Best board:");
+            for row in &best.0 {
+                match row.wildcard {
+                    Some(l) => {
+                        let letter = puzzle.alphabet.letter(l as usize);
+                        println!("{} (wildcard for {letter})", row.word);
+                    }
+                    None => println!("{}", row.word),
+                }
+            }
+            println!("Score: {}", best.1);
+        } else {
+            println!("No valid board found.");
+        }
+        best
+    } else {
+        let stdout = std::io::stdout().lock();
+        match options.format {
+            Format::Json => report.write_json(stdout),
+            Format::JsonLines => report.write_json_lines(stdout),
+            Format::Csv => report.write_csv(stdout),
+            Format::Text => unreachable!("text output is printed above"),
+        }
+        .map_err(|e| Error::io("<stdout>", e))?;
+        boards.first().cloned().unwrap_or_default()
+    };
+
+    if let Some(path) = &options.svg {
+        let svg = board_svg(&best.0, bonus_of(&best.0, &bonus_words, &puzzle), &puzzle);
+        std::fs::write(path, svg).map_err(|e| Error::io(path, e))?;
+        note(&format!("Drew the best board to {path}"));
+    }
+    if let Some(path) = &options.html {
+        let html = html_report(&report, &boards, &valid_words, &puzzle);
+        std::fs::write(path, html).map_err(|e| Error::io(path, e))?;
+        note(&format!("Wrote the solve report to {path}"));
+    }
+
+    if options.verify {
+        let progress_bar = ProgressBar::new(options.quiet);
+        let reference = best_of(Strategy::BruteForce.solve(
+            &valid_words,
+            &bonus_words,
+            &puzzle,
+            |p| progress_bar.update(p),
+        ));
+        progress_bar.finish();
+        if reference.1 != best.1 {
+            note(&format!(
+                "Verification FAILED: brute force found score {}: {}",
+                reference.1,
+                board_words(&reference.0)
+            ));
+            return Ok(false);
+        }
+        note(&format!(
+            "Verified against brute force: score {}",
+            reference.1
+        ));
+    }
+    Ok(true)
+}
+
+/// The rows of `board`, space-separated.
+fn board_words(board: &[&PackedWord]) -> String {
+    let words = board.iter().map(|w| w.word.as_str()).collect::<Vec<_>>();
+    words.join(" ")
+}
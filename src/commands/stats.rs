@@ -0,0 +1,51 @@
+use super::{Args, format_seconds, unknown_argument};
+use bongo_game::Result;
+use bongo_game::history::{self, stats};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "bongo_game stats [--history PATH]";
+
+fn days(n: usize) -> String {
+    format!("{n} day{}", if n == 1 { "" } else { "s" })
+}
+
+/// Sums up the games recorded by `play`.
+pub fn run(mut args: Args) -> Result<bool> {
+    let mut path = PathBuf::from(history::DEFAULT_PATH);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--history" => path = args.value(&arg)?.into(),
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+
+    let entries = history::load(&path)?;
+    let stats = stats(&entries, &history::today());
+    if stats.games == 0 {
+        println!("No games played yet.");
+        return Ok(true);
+    }
+    println!("Games: {} over {}", stats.games, days(stats.days));
+    println!(
+        "Current streak: {} (best {})",
+        days(stats.current_streak),
+        days(stats.best_streak)
+    );
+    print!("Average score: {:.0}", stats.average_score);
+    if let Some(percent) = stats.average_percent {
+        print!(", {percent:.1}% of the optimum");
+    }
+    println!();
+    println!(
+        "Average time: {}",
+        format_seconds(stats.average_seconds.round() as u64)
+    );
+    if let Some(best) = &stats.best {
+        print!("Best game: {} on {}", best.score, best.date);
+        if let Some(percent) = best.percent() {
+            print!(" ({percent:.1}%)");
+        }
+        println!(" in {}", format_seconds(best.seconds));
+    }
+    Ok(true)
+}
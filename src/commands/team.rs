@@ -0,0 +1,162 @@
+use super::check::{self, optimum, read_input};
+use super::{Args, LoadFlags, PuzzleFlags, unknown_argument};
+use bongo_game::dictionary::STDIN_PATH;
+use bongo_game::history::today;
+use bongo_game::leaderboard::Leaderboard;
+use bongo_game::share::SharedBoard;
+use bongo_game::solver::Strategy;
+use bongo_game::{Error, Result};
+
+pub const USAGE: &str = "bongo_game team submit --dir DIR [--player NAME] [--date YYYY-MM-DD] \
+                         [FILE|-] [-q|--quiet] [--dict PATH] [--strict] [--drop-accented] \
+                         [--alphabet LETTERS] [--feedback PATH|--no-feedback] \
+                         [--puzzle PATH|--code CODE] [--optimum SCORE] \
+                         [--strategy bonus-first|brute-force]
+       bongo_game team board --dir DIR [--date YYYY-MM-DD]";
+
+/// Where the leaderboard is and which day to look at, for both commands.
+struct Place {
+    dir: Option<String>,
+    /// Today unless given.
+    date: String,
+}
+
+impl Place {
+    fn parse(&mut self, arg: &str, args: &mut Args) -> Result<bool> {
+        match arg {
+            "--dir" => self.dir = Some(args.value(arg)?),
+            "--date" => self.date = args.value(arg)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn leaderboard(&self) -> Result<Leaderboard> {
+        let dir = self.dir.as_ref().ok_or_else(|| {
+            Error::Parse("the team's directory is needed, with --dir".to_string())
+        })?;
+        Ok(Leaderboard::new(dir))
+    }
+}
+
+pub fn run(mut args: Args) -> Result<bool> {
+    let command = args
+        .next()
+        .ok_or_else(|| Error::Parse("team needs a command".to_string()))?;
+    let mut place = Place {
+        dir: None,
+        date: today(),
+    };
+    match command.as_str() {
+        "submit" => submit(args, place),
+        "board" => {
+            while let Some(arg) = args.next() {
+                if !place.parse(&arg, &mut args)? {
+                    return Err(unknown_argument(&arg));
+                }
+            }
+            board(&place.leaderboard()?, &place.date)
+        }
+        _ => Err(Error::Parse(format!("unknown team command `{command}`"))),
+    }
+}
+
+/// Checks and scores a board, saves it as the player's board for the day, and
+/// works out the day's optimum if nobody has yet.
+fn submit(mut args: Args, mut place: Place) -> Result<bool> {
+    let mut player = std::env::var("USER").ok();
+    let mut options = check::Options {
+        quiet: false,
+        input: STDIN_PATH.to_string(),
+        dict: "bongo-common-words.txt".to_string(),
+        load: LoadFlags::default(),
+        puzzle: PuzzleFlags::default(),
+        optimum: None,
+        strategy: Strategy::BonusFirst,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--player" => player = Some(args.value(&arg)?),
+            "-q" | "--quiet" => options.quiet = true,
+            "--dict" => options.dict = args.value(&arg)?,
+            "--optimum" => options.optimum = Some(args.parsed(&arg)?),
+            "--strategy" => options.strategy = args.value(&arg)?.parse()?,
+            _ if place.parse(&arg, &mut args)? => {}
+            _ if options.load.parse(&arg, &mut args)? => {}
+            _ if options.puzzle.parse(&arg, &mut args)? => {}
+            _ if arg == STDIN_PATH || !arg.starts_with('-') => options.input = arg,
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    let player =
+        player.ok_or_else(|| Error::Parse("who's playing? give --player NAME".to_string()))?;
+    let leaderboard = place.leaderboard()?;
+    let puzzle = options.puzzle.load()?;
+    options.load.use_puzzle_alphabet(&puzzle);
+    let shared = read_input(&options.input)?.parse::<SharedBoard>()?;
+    let (dictionary, summary) = options.load.open(&options.dict)?;
+    print!("{summary}");
+
+    let submission =
+        leaderboard.submit(&player, &place.date, &puzzle, &shared, dictionary.as_ref())?;
+    println!(
+        "{} scored {} on {}.",
+        submission.player, submission.score, submission.date
+    );
+    let known = leaderboard
+        .day_puzzle(&place.date)?
+        .and_then(|day| day.optimum);
+    if known.is_none() {
+        let (_, optimum) = optimum(&options, dictionary.as_ref(), &puzzle);
+        if optimum > 0 {
+            leaderboard.set_optimum(&place.date, optimum)?;
+        }
+    }
+    println!();
+    board(&leaderboard, &place.date)
+}
+
+/// Prints the day's ranks and the week's totals.
+fn board(leaderboard: &Leaderboard, date: &str) -> Result<bool> {
+    let daily = leaderboard.daily(date)?;
+    let optimum = leaderboard.day_puzzle(date)?.and_then(|day| day.optimum);
+    let width = daily
+        .iter()
+        .map(|r| r.player.chars().count())
+        .max()
+        .unwrap_or(0);
+    match optimum {
+        Some(optimum) => println!("{date} · optimum {optimum}"),
+        None => println!("{date}"),
+    }
+    if daily.is_empty() {
+        println!("  No boards yet.");
+    }
+    for rank in &daily {
+        let gap = match rank.gap {
+            Some(0) => "  optimal".to_string(),
+            Some(gap) => format!("  -{gap}"),
+            None => String::new(),
+        };
+        println!(
+            "{:>3}. {:<width$} {:>5}{gap}",
+            rank.rank, rank.player, rank.score
+        );
+    }
+
+    let week = Leaderboard::week_of(date)?;
+    let weekly = leaderboard.weekly(date)?;
+    let width = weekly
+        .iter()
+        .map(|t| t.player.chars().count())
+        .max()
+        .unwrap_or(0);
+    println!("\nWeek of {}", week[0]);
+    for total in &weekly {
+        println!(
+            "{:>3}. {:<width$} {:>6}  ({} of 7 days)",
+            total.rank, total.player, total.total, total.days
+        );
+    }
+    Ok(true)
+}
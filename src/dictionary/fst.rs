@@ -0,0 +1,80 @@
+//! Words stored in a finite state transducer, which shares prefixes and
+//! suffixes between words so large lists stay small on disk and in memory.
+//! Each word maps to 1 if it's common and 0 if not.
+
+use super::Dictionary;
+use crate::error::{Error, Result};
+use fst::{IntoStreamer, Map, MapBuilder, Streamer};
+use std::path::Path;
+
+#[derive(Clone)]
+pub struct FstDictionary {
+    map: Map<Vec<u8>>,
+}
+
+impl FstDictionary {
+    pub fn compile(dictionary: &dyn Dictionary) -> Self {
+        let mut words = dictionary
+            .words()
+            .map(|w| {
+                let common = dictionary.is_common(&w);
+                (w, common)
+            })
+            .collect::<Vec<_>>();
+        words.sort();
+        words.dedup_by(|a, b| a.0 == b.0);
+        let mut builder = MapBuilder::memory();
+        for (word, common) in words {
+            builder
+                .insert(word, common as u64)
+                .expect("words are inserted sorted and unique");
+        }
+        let bytes = builder.into_inner().expect("writing to memory can't fail");
+        Self::from_bytes(bytes).expect("a freshly built FST is well formed")
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| Error::io(path, e))?;
+        Self::from_bytes(bytes).map_err(|e| Error::Parse(format!("{}: {e}", path.display())))
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        let map = Map::new(bytes).map_err(|e| Error::Parse(format!("not a word FST: {e}")))?;
+        Ok(FstDictionary { map })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.map.as_fst().as_bytes()
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.as_bytes()).map_err(|e| Error::io(path, e))
+    }
+}
+
+impl Dictionary for FstDictionary {
+    fn contains(&self, word: &str) -> bool {
+        self.map.contains_key(word)
+    }
+
+    fn is_common(&self, word: &str) -> bool {
+        self.map.get(word) == Some(1)
+    }
+
+    /// Alphabetically.
+    fn words(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        let mut words = vec![];
+        let mut stream = self.map.into_stream();
+        while let Some((word, _)) = stream.next() {
+            // Only ever built from whole words
+            words.push(String::from_utf8_lossy(word).into_owned());
+        }
+        Box::new(words.into_iter())
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
@@ -0,0 +1,301 @@
+//! Importing Hunspell dictionaries: a `.dic` file of stems tagged with affix
+//! flags, and a `.aff` file saying what each flag adds or strips. Every stem is
+//! expanded into its inflected forms (`WALK/DGS` gives WALK, WALKED, WALKING and
+//! WALKS), which then go through the usual clean-up.
+//!
+//! Supported: `SET` (UTF-8, the ISO 8859 encodings, KOI8-R, KOI8-U,
+//! `microsoft-cp1251` and TIS-620), `FLAG` (`long`, `num` and `UTF-8`),
+//! `PFX`/`SFX` with conditions and cross products, and the `NEEDAFFIX`,
+//! `FORBIDDENWORD` and `ONLYINCOMPOUND` flags. Continuation flags on affixes
+//! (`SFX A 0 s/B`) are ignored, so each form gets at most one prefix and one
+//! suffix. Stems starting with a capital letter can be left out as proper
+//! nouns, which suits English but not German, where every noun has one.
+
+use super::{Collector, LoadOptions, WordList};
+use crate::error::{Error, Result};
+use encoding_rs::Encoding;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// What to do with stems that start with a capital letter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Capitalized {
+    /// Leave them out as proper nouns, which aren't playable.
+    #[default]
+    Skip,
+    /// Keep them, for languages that capitalize common nouns too.
+    Keep,
+}
+
+/// A flag, however the affix file spells it.
+type Flag = u32;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum FlagType {
+    /// One character per flag.
+    #[default]
+    Short,
+    /// Two characters per flag.
+    Long,
+    /// Comma separated numbers.
+    Num,
+}
+
+impl FlagType {
+    fn parse(self, flags: &str) -> Vec<Flag> {
+        match self {
+            FlagType::Short => flags.chars().map(|c| c as Flag).collect(),
+            FlagType::Long => flags
+                .chars()
+                .collect::<Vec<_>>()
+                .chunks(2)
+                .map(|pair| pair.iter().fold(0, |flag, &c| (flag << 16) | c as Flag))
+                .collect(),
+            FlagType::Num => flags
+                .split(',')
+                .filter_map(|n| n.trim().parse().ok())
+                .collect(),
+        }
+    }
+
+    fn parse_one(self, flag: &str) -> Option<Flag> {
+        self.parse(flag).first().copied()
+    }
+}
+
+/// One position of an affix condition.
+#[derive(Clone, Debug)]
+enum CharClass {
+    Any,
+    OneOf(Vec<char>),
+    NoneOf(Vec<char>),
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CharClass::Any => true,
+            CharClass::OneOf(chars) => chars.contains(&c),
+            CharClass::NoneOf(chars) => !chars.contains(&c),
+        }
+    }
+}
+
+fn parse_condition(condition: &str) -> Vec<CharClass> {
+    let mut classes = vec![];
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        classes.push(match c {
+            '.' => CharClass::Any,
+            '[' => {
+                let set = chars.by_ref().take_while(|&c| c != ']').collect::<String>();
+                match set.strip_prefix('^') {
+                    Some(set) => CharClass::NoneOf(set.chars().collect()),
+                    None => CharClass::OneOf(set.chars().collect()),
+                }
+            }
+            c => CharClass::OneOf(vec![c]),
+        });
+    }
+    classes
+}
+
+#[derive(Clone, Debug)]
+struct Affix {
+    strip: String,
+    add: String,
+    condition: Vec<CharClass>,
+}
+
+impl Affix {
+    /// Applies the affix to the end of `word`, if it fits.
+    fn suffix(&self, word: &str) -> Option<String> {
+        let chars = word.chars().collect::<Vec<_>>();
+        let start = chars.len().checked_sub(self.condition.len())?;
+        let fits = chars[start..]
+            .iter()
+            .zip(&self.condition)
+            .all(|(&c, class)| class.matches(c));
+        let stem = word.strip_suffix(self.strip.as_str())?;
+        (fits && !stem.is_empty()).then(|| format!("{stem}{}", self.add))
+    }
+
+    /// Applies the affix to the start of `word`, if it fits.
+    fn prefix(&self, word: &str) -> Option<String> {
+        let fits = word.chars().count() >= self.condition.len()
+            && word
+                .chars()
+                .zip(&self.condition)
+                .all(|(c, class)| class.matches(c));
+        let stem = word.strip_prefix(self.strip.as_str())?;
+        (fits && !stem.is_empty()).then(|| format!("{}{stem}", self.add))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct AffixClass {
+    /// Whether the class combines with affixes of the other kind.
+    cross_product: bool,
+    affixes: Vec<Affix>,
+}
+
+/// The rules of a `.aff` file.
+#[derive(Clone, Debug, Default)]
+pub struct Affixes {
+    flag_type: FlagType,
+    prefixes: HashMap<Flag, AffixClass>,
+    suffixes: HashMap<Flag, AffixClass>,
+    /// Flags marking stems that aren't words on their own.
+    not_alone: Vec<Flag>,
+    /// Flag marking stems that aren't words at all.
+    forbidden: Option<Flag>,
+}
+
+impl Affixes {
+    pub fn parse(aff: &str) -> Result<Self> {
+        let mut affixes = Affixes::default();
+        for (i, line) in aff.lines().enumerate() {
+            let bad = |what: &str| Error::Parse(format!("affix line {}: {what}", i + 1));
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            match fields.as_slice() {
+                ["FLAG", "long", ..] => affixes.flag_type = FlagType::Long,
+                ["FLAG", "num", ..] => affixes.flag_type = FlagType::Num,
+                ["NEEDAFFIX" | "ONLYINCOMPOUND", flag, ..] => {
+                    affixes.not_alone.extend(affixes.flag_type.parse_one(flag));
+                }
+                ["FORBIDDENWORD", flag, ..] => {
+                    affixes.forbidden = affixes.flag_type.parse_one(flag);
+                }
+                [kind @ ("PFX" | "SFX"), flag, rest @ ..] => {
+                    let flag = affixes
+                        .flag_type
+                        .parse_one(flag)
+                        .ok_or_else(|| bad("missing flag"))?;
+                    let classes = if *kind == "PFX" {
+                        &mut affixes.prefixes
+                    } else {
+                        &mut affixes.suffixes
+                    };
+                    match rest {
+                        // Header: cross product and rule count
+                        [cross @ ("Y" | "N"), count] if count.parse::<usize>().is_ok() => {
+                            classes.entry(flag).or_default().cross_product = *cross == "Y";
+                        }
+                        [strip, add, rest @ ..] => {
+                            let none = |s: &str| if s == "0" { "" } else { s }.to_string();
+                            // Continuation flags after the `/` aren't supported
+                            let add = add.split('/').next().unwrap_or_default();
+                            classes.entry(flag).or_default().affixes.push(Affix {
+                                strip: none(strip),
+                                add: none(add),
+                                condition: parse_condition(rest.first().unwrap_or(&".")),
+                            });
+                        }
+                        _ => return Err(bad("expected `strip add [condition]`")),
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(affixes)
+    }
+
+    /// Every word form of a `.dic` entry, stem first.
+    pub fn expand(&self, entry: &str) -> Vec<String> {
+        let (stem, flags) = match entry.split_once('/') {
+            Some((stem, flags)) => (stem, self.flag_type.parse(flags)),
+            None => (entry, vec![]),
+        };
+        if self.forbidden.is_some_and(|f| flags.contains(&f)) {
+            return vec![];
+        }
+        let mut forms = vec![];
+        if !flags.iter().any(|f| self.not_alone.contains(f)) {
+            forms.push(stem.to_string());
+        }
+        let prefixes = flags
+            .iter()
+            .filter_map(|f| self.prefixes.get(f))
+            .collect::<Vec<_>>();
+        for class in flags.iter().filter_map(|f| self.suffixes.get(f)) {
+            for word in class.affixes.iter().filter_map(|a| a.suffix(stem)) {
+                if class.cross_product {
+                    for prefix in prefixes.iter().filter(|p| p.cross_product) {
+                        forms.extend(prefix.affixes.iter().filter_map(|a| a.prefix(&word)));
+                    }
+                }
+                forms.push(word);
+            }
+        }
+        for class in prefixes {
+            forms.extend(class.affixes.iter().filter_map(|a| a.prefix(stem)));
+        }
+        forms
+    }
+}
+
+/// Decodes a Hunspell file in the encoding named by the affix file's `SET`, or
+/// `None` if it's one this can't decode.
+fn decode(bytes: &[u8], set: &str) -> Option<String> {
+    // Hunspell's name for Windows-1251 isn't one of the standard labels
+    let label = match set {
+        "MICROSOFT-CP1251" => "WINDOWS-1251",
+        "TIS620-2533" => "TIS-620",
+        _ => set,
+    };
+    let (text, _) = Encoding::for_label(label.as_bytes())?.decode_with_bom_removal(bytes);
+    Some(text.into_owned())
+}
+
+fn encoding(aff: &[u8]) -> String {
+    String::from_utf8_lossy(aff)
+        .lines()
+        .find_map(|line| line.strip_prefix("SET "))
+        .map_or_else(|| "UTF-8".to_string(), |set| set.trim().to_uppercase())
+}
+
+/// Reads a Hunspell `.dic` and `.aff` pair, expanding every stem.
+pub fn load_hunspell(
+    dic: impl AsRef<Path>,
+    aff: impl AsRef<Path>,
+    capitalized: Capitalized,
+    options: LoadOptions,
+) -> Result<WordList> {
+    let (dic, aff) = (dic.as_ref(), aff.as_ref());
+    let aff_bytes = std::fs::read(aff).map_err(|e| Error::io(aff, e))?;
+    let dic_bytes = std::fs::read(dic).map_err(|e| Error::io(dic, e))?;
+    let encoding = encoding(&aff_bytes);
+    let unsupported = || Error::Parse(format!("{}: can't decode SET {encoding}", aff.display()));
+    let aff_text = decode(&aff_bytes, &encoding).ok_or_else(unsupported)?;
+    let dic_text = decode(&dic_bytes, &encoding).ok_or_else(unsupported)?;
+    let affixes =
+        Affixes::parse(&aff_text).map_err(|e| Error::Parse(format!("{}: {e}", aff.display())))?;
+    read_hunspell(&dic_text, &affixes, dic, capitalized, options)
+}
+
+/// Expands the entries of `dic` with `affixes`. `path` is only used to label
+/// bad lines, which are numbered by the entry they were expanded from.
+pub fn read_hunspell(
+    dic: &str,
+    affixes: &Affixes,
+    path: impl AsRef<Path>,
+    capitalized: Capitalized,
+    options: LoadOptions,
+) -> Result<WordList> {
+    let mut collector = Collector::new(path.as_ref(), options);
+    for (i, line) in dic.lines().enumerate() {
+        // The first line is the entry count; anything after the entry is
+        // morphological data
+        let Some(entry) = line.split_whitespace().next() else {
+            continue;
+        };
+        let proper_noun = capitalized == Capitalized::Skip && entry.starts_with(char::is_uppercase);
+        if (i == 0 && entry.parse::<usize>().is_ok()) || proper_noun {
+            continue;
+        }
+        for form in affixes.expand(entry) {
+            collector.add(i + 1, form)?;
+        }
+    }
+    Ok(collector.list)
+}
@@ -0,0 +1,194 @@
+//! The compiled binary index: words bucketed by length in bytes and sorted
+//! within each bucket, so lookups are a binary search over fixed-size records with no
+//! parsing at load time.
+//!
+//! Layout, with counts as little-endian `u32`s:
+//!
+//! ```text
+//! MAGIC
+//! longest word length in bytes L (u8)
+//! L word counts, for lengths 1 to L
+//! for each length: the sorted words back to back, then one bit per word
+//!                  (lowest bit first) saying whether it's common
+//! ```
+
+use super::Dictionary;
+use crate::alphabet::Alphabet;
+use crate::error::{Error, Result};
+use crate::puzzle::LetterBag;
+use std::cmp::Ordering;
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"BNGIDX01";
+
+/// Longest word the index can hold, in bytes, since lengths are stored in a byte.
+pub const MAX_INDEXED_LEN: usize = u8::MAX as usize;
+
+/// Most bytes a letter takes in UTF-8.
+const MAX_UTF8_LEN: usize = 4;
+
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    start: usize,
+    count: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct IndexDictionary {
+    bytes: Vec<u8>,
+    /// `buckets[len - 1]` holds the words of `len` letters.
+    buckets: Vec<Bucket>,
+}
+
+impl IndexDictionary {
+    /// Compiles the words of `dictionary`, leaving out any longer than
+    /// `MAX_INDEXED_LEN`.
+    pub fn compile(dictionary: &dyn Dictionary) -> Self {
+        let mut by_length: Vec<Vec<(String, bool)>> = vec![];
+        for word in dictionary.words() {
+            let len = word.len();
+            if len == 0 || len > MAX_INDEXED_LEN {
+                continue;
+            }
+            if by_length.len() < len {
+                by_length.resize(len, vec![]);
+            }
+            let common = dictionary.is_common(&word);
+            by_length[len - 1].push((word, common));
+        }
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(by_length.len() as u8);
+        for words in &mut by_length {
+            words.sort();
+            words.dedup_by(|a, b| a.0 == b.0);
+            bytes.extend((words.len() as u32).to_le_bytes());
+        }
+        for words in &by_length {
+            for (word, _) in words {
+                bytes.extend(word.as_bytes());
+            }
+            let mut bits = vec![0u8; words.len().div_ceil(8)];
+            for (i, _) in words.iter().enumerate().filter(|(_, (_, common))| *common) {
+                bits[i / 8] |= 1 << (i % 8);
+            }
+            bytes.extend(bits);
+        }
+        Self::from_bytes(bytes).expect("a freshly compiled index is well formed")
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| Error::io(path, e))?;
+        Self::from_bytes(bytes).map_err(|e| Error::Parse(format!("{}: {e}", path.display())))
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        let bad = |what: &str| Error::Parse(format!("not a word index: {what}"));
+        if bytes.len() < MAGIC.len() + 1 || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(bad("bad header"));
+        }
+        let lengths = bytes[MAGIC.len()] as usize;
+        let mut start = MAGIC.len() + 1 + 4 * lengths;
+        if bytes.len() < start {
+            return Err(bad("truncated header"));
+        }
+        let mut buckets = Vec::with_capacity(lengths);
+        for len in 1..=lengths {
+            let at = MAGIC.len() + 1 + 4 * (len - 1);
+            let count = u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as usize;
+            buckets.push(Bucket { start, count });
+            start += count * len + count.div_ceil(8);
+        }
+        if bytes.len() != start {
+            return Err(bad("size doesn't match its word counts"));
+        }
+        Ok(IndexDictionary { bytes, buckets })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, &self.bytes).map_err(|e| Error::io(path, e))
+    }
+
+    fn bucket(&self, len: usize) -> Option<Bucket> {
+        len.checked_sub(1)
+            .and_then(|i| self.buckets.get(i))
+            .copied()
+    }
+
+    fn word(&self, len: usize, bucket: Bucket, i: usize) -> &[u8] {
+        let at = bucket.start + i * len;
+        &self.bytes[at..at + len]
+    }
+
+    /// The words of `len` bytes.
+    fn bucket_words(&self, len: usize) -> impl Iterator<Item = String> + '_ {
+        let bucket = self.bucket(len).unwrap_or(Bucket { start: 0, count: 0 });
+        (0..bucket.count).map(move |i| {
+            // Only ever built from whole words
+            String::from_utf8_lossy(self.word(len, bucket, i)).into_owned()
+        })
+    }
+
+    /// Position of `word` within its bucket.
+    fn find(&self, word: &str) -> Option<(Bucket, usize)> {
+        let len = word.len();
+        let bucket = self.bucket(len)?;
+        let (mut lo, mut hi) = (0, bucket.count);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.word(len, bucket, mid).cmp(word.as_bytes()) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => return Some((bucket, mid)),
+            }
+        }
+        None
+    }
+}
+
+impl Dictionary for IndexDictionary {
+    fn contains(&self, word: &str) -> bool {
+        self.find(word).is_some()
+    }
+
+    fn is_common(&self, word: &str) -> bool {
+        self.find(word).is_some_and(|(bucket, i)| {
+            let bits = bucket.start + bucket.count * word.len();
+            self.bytes[bits + i / 8] & (1 << (i % 8)) != 0
+        })
+    }
+
+    /// Shortest words first, in byte order within each length.
+    fn words(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new((1..=self.buckets.len()).flat_map(|len| self.bucket_words(len)))
+    }
+
+    fn len(&self) -> usize {
+        self.buckets.iter().map(|b| b.count).sum()
+    }
+
+    fn words_of_length(&self, len: usize) -> Box<dyn Iterator<Item = String> + '_> {
+        // Buckets are by length in bytes, and letters outside A-Z take more than one
+        let longest = (len * MAX_UTF8_LEN).min(self.buckets.len());
+        Box::new(
+            (len..=longest)
+                .flat_map(|bytes| self.bucket_words(bytes))
+                .filter(move |w| w.chars().count() == len),
+        )
+    }
+
+    /// Skips the buckets of words longer than the bag.
+    fn words_fitting(&self, bag: &LetterBag, alphabet: &Alphabet) -> Vec<String> {
+        let tiles = bag.iter().map(|&n| n as usize).sum::<usize>();
+        (1..=(tiles * MAX_UTF8_LEN).min(self.buckets.len()))
+            .flat_map(|bytes| self.bucket_words(bytes))
+            .filter(|w| super::fits_bag(w, bag, alphabet))
+            .collect()
+    }
+}
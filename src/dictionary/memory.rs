@@ -0,0 +1,47 @@
+use super::Dictionary;
+use std::collections::HashMap;
+
+/// Words held in memory, mostly for tests and for lists built on the fly.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryDictionary {
+    words: Vec<String>,
+    /// Whether each word is common.
+    common: HashMap<String, bool>,
+}
+
+impl MemoryDictionary {
+    /// A dictionary where every one of `words` is common.
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = S>) -> Self {
+        let mut dictionary = MemoryDictionary::default();
+        for word in words {
+            dictionary.insert(word, true);
+        }
+        dictionary
+    }
+
+    /// Adds `word`, or updates whether it's common if it's already there.
+    pub fn insert(&mut self, word: impl Into<String>, common: bool) {
+        let word = word.into();
+        if self.common.insert(word.clone(), common).is_none() {
+            self.words.push(word);
+        }
+    }
+}
+
+impl Dictionary for MemoryDictionary {
+    fn contains(&self, word: &str) -> bool {
+        self.common.contains_key(word)
+    }
+
+    fn is_common(&self, word: &str) -> bool {
+        self.common.get(word).copied().unwrap_or(false)
+    }
+
+    fn words(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(self.words.iter().cloned())
+    }
+
+    fn len(&self) -> usize {
+        self.words.len()
+    }
+}
@@ -0,0 +1,413 @@
+//! Loading word lists. Every source goes through the same clean-up: trim,
+//! uppercase, fold accents the alphabet doesn't have, drop anything that still
+//! isn't a word of the alphabet, and drop duplicates,
+//! with counts of what happened kept in `LoadStats`. Lists can be read from
+//! gzip or zstd compressed files, or from stdin.
+//!
+//! The solver reads words through the `Dictionary` trait, which is implemented
+//! for plain text lists, the compiled binary index, FSTs and in-memory lists.
+
+mod fst;
+mod hunspell;
+mod index;
+mod memory;
+mod text;
+
+pub use fst::FstDictionary;
+pub use hunspell::{Affixes, Capitalized, load_hunspell, read_hunspell};
+pub use index::IndexDictionary;
+pub use memory::MemoryDictionary;
+pub use text::TextDictionary;
+
+use crate::alphabet::Alphabet;
+use crate::error::{BadLine, BadLineReason, Error, Result};
+use crate::packed::WILDCARD;
+use crate::puzzle::LetterBag;
+use flate2::read::MultiGzDecoder;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// A set of normalized words, some of which the game counts as common.
+pub trait Dictionary: Sync {
+    fn contains(&self, word: &str) -> bool;
+
+    /// Whether the game counts `word` as a common word.
+    fn is_common(&self, word: &str) -> bool;
+
+    /// Every word, in the dictionary's own order.
+    fn words(&self) -> Box<dyn Iterator<Item = String> + '_>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The words of exactly `len` letters, in the dictionary's own order.
+    fn words_of_length(&self, len: usize) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(self.words().filter(move |w| w.chars().count() == len))
+    }
+
+    /// The words that can be spelled from `bag`, using its wildcards for
+    /// missing letters.
+    fn words_fitting(&self, bag: &LetterBag, alphabet: &Alphabet) -> Vec<String> {
+        self.words()
+            .filter(|w| fits_bag(w, bag, alphabet))
+            .collect()
+    }
+}
+
+/// Whether `word` can be spelled from `bag`, using its wildcards for missing
+/// letters.
+pub fn fits_bag(word: &str, bag: &LetterBag, alphabet: &Alphabet) -> bool {
+    let mut bag = *bag;
+    let mut missing = 0;
+    for c in word.chars() {
+        match alphabet.index_of(c) {
+            Some(i) => {
+                if bag[i] > 0 {
+                    bag[i] -= 1;
+                } else {
+                    missing += 1;
+                }
+            }
+            _ => return false,
+        }
+    }
+    missing <= bag[WILDCARD]
+}
+
+/// What to do with dictionary lines that aren't words of the alphabet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Leave them out and report them in `WordList::skipped`.
+    #[default]
+    Skip,
+    /// Fail the load on the first one.
+    Reject,
+}
+
+/// What to do with accented letters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Diacritics {
+    /// Fold the ones outside the alphabet to their base letter, so `CAFÉ`
+    /// becomes `CAFE` unless the alphabet has `É`.
+    #[default]
+    Fold,
+    /// Treat the ones outside the alphabet like any other bad character.
+    Drop,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    pub strictness: Strictness,
+    pub diacritics: Diacritics,
+    /// Letters words are made of. Others are folded into it or rejected.
+    pub alphabet: Alphabet,
+}
+
+/// Counts from one pass of the loading pipeline.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoadStats {
+    pub lines: usize,
+    pub blank: usize,
+    /// Words that only made it in by folding accents.
+    pub folded: usize,
+    pub duplicates: usize,
+    pub skipped: usize,
+    /// Number of kept words of each length.
+    pub by_length: BTreeMap<usize, usize>,
+}
+
+/// Words read from a dictionary, normalized and deduplicated, plus the lines
+/// that were skipped.
+#[derive(Clone, Debug, Default)]
+pub struct WordList {
+    pub words: Vec<String>,
+    pub skipped: Vec<BadLine>,
+    pub stats: LoadStats,
+}
+
+/// Path that reads from stdin instead of a file.
+pub const STDIN_PATH: &str = "-";
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Reads the word list at `path`, or stdin for `-`.
+pub fn load_words(path: impl AsRef<Path>, options: LoadOptions) -> Result<WordList> {
+    let path = path.as_ref();
+    if path == Path::new(STDIN_PATH) {
+        let stdin = Path::new("<stdin>");
+        return read_words(decompress(io::stdin().lock(), stdin)?, stdin, options);
+    }
+    let file = File::open(path).map_err(|e| Error::io(path, e))?;
+    read_words(decompress(file, path)?, path, options)
+}
+
+/// Wraps `input` in a gzip or zstd decoder if it starts with their magic
+/// bytes, so compressed lists work whatever they're called.
+fn decompress(input: impl Read + 'static, path: &Path) -> Result<Box<dyn BufRead>> {
+    let mut input = BufReader::new(input);
+    let magic = input.fill_buf().map_err(|e| Error::io(path, e))?;
+    Ok(if magic.starts_with(&GZIP_MAGIC) {
+        Box::new(BufReader::new(MultiGzDecoder::new(input)))
+    } else if magic.starts_with(&ZSTD_MAGIC) {
+        let decoder = zstd::Decoder::with_buffer(input).map_err(|e| Error::io(path, e))?;
+        Box::new(BufReader::new(decoder))
+    } else {
+        Box::new(input)
+    })
+}
+
+/// Reads one word per line. `path` is only used to label bad lines.
+pub fn read_words(
+    reader: impl BufRead,
+    path: impl AsRef<Path>,
+    options: LoadOptions,
+) -> Result<WordList> {
+    let path = path.as_ref();
+    let mut collector = Collector::new(path, options);
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| Error::io(path, e))?;
+        collector.add(i + 1, line)?;
+    }
+    Ok(collector.list)
+}
+
+/// Runs raw words through the clean-up one at a time, building up a `WordList`.
+struct Collector<'a> {
+    path: &'a Path,
+    options: LoadOptions,
+    list: WordList,
+    seen: HashSet<String>,
+}
+
+impl<'a> Collector<'a> {
+    fn new(path: &'a Path, options: LoadOptions) -> Self {
+        Collector {
+            path,
+            options,
+            list: WordList::default(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Adds `text`, which came from line `line` of the source.
+    fn add(&mut self, line: usize, text: String) -> Result<()> {
+        let list = &mut self.list;
+        list.stats.lines += 1;
+        match normalize_word(&text, self.options.diacritics, &self.options.alphabet) {
+            Ok(None) => list.stats.blank += 1,
+            Ok(Some((word, folded))) => {
+                if !self.seen.insert(word.clone()) {
+                    list.stats.duplicates += 1;
+                    return Ok(());
+                }
+                list.stats.folded += folded as usize;
+                *list
+                    .stats
+                    .by_length
+                    .entry(word.chars().count())
+                    .or_default() += 1;
+                list.words.push(word);
+            }
+            Err(reason) => {
+                let bad_line = BadLine {
+                    path: self.path.to_path_buf(),
+                    line,
+                    text,
+                    reason,
+                };
+                match self.options.strictness {
+                    Strictness::Skip => {
+                        list.stats.skipped += 1;
+                        list.skipped.push(bad_line);
+                    }
+                    Strictness::Reject => return Err(Error::BadLine(bad_line)),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Turns a raw line into a word of `alphabet`, or `None` for a blank line. The
+/// flag says whether accents had to be folded to get there.
+pub fn normalize_word(
+    raw: &str,
+    diacritics: Diacritics,
+    alphabet: &Alphabet,
+) -> std::result::Result<Option<(String, bool)>, BadLineReason> {
+    // Compose first, so an N and a combining tilde is the alphabet's Ñ
+    let word = raw.trim().nfc().collect::<String>().to_uppercase();
+    if word.is_empty() {
+        return Ok(None);
+    }
+    let mut normalized = String::with_capacity(word.len());
+    let mut folded = false;
+    for c in word.chars() {
+        if alphabet.contains(c) {
+            normalized.push(c);
+            continue;
+        }
+        let base = fold_diacritics(c);
+        if diacritics == Diacritics::Drop
+            || base == [c]
+            || !base.iter().all(|&b| alphabet.contains(b))
+        {
+            // Report the character as it was written, not its decomposition
+            return Err(BadLineReason::InvalidChar(c));
+        }
+        normalized.extend(base);
+        folded = true;
+    }
+    Ok(Some((normalized, folded)))
+}
+
+fn fold_diacritics(c: char) -> Vec<char> {
+    std::iter::once(c)
+        .nfd()
+        .filter(|&c| !is_combining_mark(c))
+        .flat_map(|c| {
+            // Letters that don't decompose into a base letter and a mark
+            let folded = match c {
+                'Æ' => "AE",
+                'Œ' => "OE",
+                'Ø' => "O",
+                'Ł' => "L",
+                'Đ' => "D",
+                'Þ' => "TH",
+                _ => return vec![c],
+            };
+            folded.chars().collect()
+        })
+        .collect()
+}
+
+impl fmt::Display for LoadStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kept: usize = self.by_length.values().sum();
+        writeln!(f, "Lines: {}, words kept: {kept}", self.lines)?;
+        writeln!(
+            f,
+            "Blank: {}, duplicates: {}, skipped: {}, accents folded: {}",
+            self.blank, self.duplicates, self.skipped, self.folded
+        )?;
+        for (len, count) in &self.by_length {
+            writeln!(f, "  {len} letters: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Every word in any of the lists, in the order first seen.
+pub fn merge<'a>(lists: impl IntoIterator<Item = &'a [String]>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    lists
+        .into_iter()
+        .flatten()
+        .filter(|w| seen.insert(w.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Words of `list` that are in every one of `others`.
+pub fn intersect(list: &[String], others: &[&[String]]) -> Vec<String> {
+    let others = others
+        .iter()
+        .map(|o| o.iter().collect::<HashSet<_>>())
+        .collect::<Vec<_>>();
+    list.iter()
+        .filter(|w| others.iter().all(|o| o.contains(w)))
+        .cloned()
+        .collect()
+}
+
+/// Words of `list` that are in none of `others`.
+pub fn difference(list: &[String], others: &[&[String]]) -> Vec<String> {
+    let others = others.iter().copied().flatten().collect::<HashSet<_>>();
+    list.iter()
+        .filter(|w| !others.contains(w))
+        .cloned()
+        .collect()
+}
+
+/// A letter pattern like `B?N*`: `?` matches any one letter, `*` any run of
+/// letters (including none), and anything else matches itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern(Vec<char>);
+
+impl Pattern {
+    pub fn matches(&self, word: &str) -> bool {
+        let word = word.chars().collect::<Vec<_>>();
+        // match_from[j]: whether the pattern so far can end just before word[j]
+        let mut match_from = vec![false; word.len() + 1];
+        match_from[0] = true;
+        for &p in &self.0 {
+            let mut next = vec![false; word.len() + 1];
+            for j in 0..=word.len() {
+                next[j] = match p {
+                    '*' => match_from[j] || (j > 0 && next[j - 1]),
+                    '?' => j > 0 && match_from[j - 1],
+                    c => j > 0 && match_from[j - 1] && word[j - 1] == c,
+                };
+            }
+            match_from = next;
+        }
+        match_from[word.len()]
+    }
+}
+
+impl std::str::FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let pattern = s.to_uppercase().chars().collect::<Vec<_>>();
+        if let Some(c) = pattern
+            .iter()
+            .find(|&&c| !((c.is_alphabetic() && c.is_uppercase()) || c == '?' || c == '*'))
+        {
+            return Err(Error::Parse(format!(
+                "bad pattern `{s}`: unexpected {c:?}, use letters, `?` and `*`"
+            )));
+        }
+        Ok(Pattern(pattern))
+    }
+}
+
+/// Conditions a word has to meet to be kept by `filter`. Unset fields don't
+/// constrain anything.
+#[derive(Clone, Debug, Default)]
+pub struct WordFilter {
+    pub min_len: Option<usize>,
+    pub max_len: Option<usize>,
+    pub pattern: Option<Pattern>,
+    /// Only keep words also in this list.
+    pub member_of: Option<HashSet<String>>,
+    /// Drop words in this list.
+    pub not_member_of: Option<HashSet<String>>,
+}
+
+impl WordFilter {
+    pub fn matches(&self, word: &str) -> bool {
+        let len = word.chars().count();
+        self.min_len.is_none_or(|min| len >= min)
+            && self.max_len.is_none_or(|max| len <= max)
+            && self.pattern.as_ref().is_none_or(|p| p.matches(word))
+            && self.member_of.as_ref().is_none_or(|m| m.contains(word))
+            && self
+                .not_member_of
+                .as_ref()
+                .is_none_or(|m| !m.contains(word))
+    }
+
+    pub fn filter(&self, words: &[String]) -> Vec<String> {
+        words.iter().filter(|w| self.matches(w)).cloned().collect()
+    }
+}
@@ -0,0 +1,49 @@
+use super::{Dictionary, LoadOptions, WordList, load_words};
+use crate::error::Result;
+use std::path::Path;
+
+/// A plain text word list, one word per line. The lists the game is played
+/// with only hold common words, so every word counts as common.
+#[derive(Clone, Debug)]
+pub struct TextDictionary {
+    list: WordList,
+    /// Positions in `list.words`, sorted by word for lookups.
+    sorted: Vec<u32>,
+}
+
+impl TextDictionary {
+    pub fn load(path: impl AsRef<Path>, options: LoadOptions) -> Result<Self> {
+        Ok(Self::from_word_list(load_words(path, options)?))
+    }
+
+    pub fn from_word_list(list: WordList) -> Self {
+        let mut sorted = (0..list.words.len() as u32).collect::<Vec<_>>();
+        sorted.sort_by(|&a, &b| list.words[a as usize].cmp(&list.words[b as usize]));
+        TextDictionary { list, sorted }
+    }
+
+    /// What loading the list did, including the lines it skipped.
+    pub fn word_list(&self) -> &WordList {
+        &self.list
+    }
+}
+
+impl Dictionary for TextDictionary {
+    fn contains(&self, word: &str) -> bool {
+        self.sorted
+            .binary_search_by(|&i| self.list.words[i as usize].as_str().cmp(word))
+            .is_ok()
+    }
+
+    fn is_common(&self, word: &str) -> bool {
+        self.contains(word)
+    }
+
+    fn words(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(self.list.words.iter().cloned())
+    }
+
+    fn len(&self) -> usize {
+        self.list.words.len()
+    }
+}
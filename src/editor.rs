@@ -0,0 +1,172 @@
+//! What's behind the full-screen board editor: letters typed into a grid
+//! under a cursor, with undo and redo, and checked row by row as a `Game`.
+
+use crate::alphabet::MAX_LETTERS;
+use crate::dictionary::Dictionary;
+use crate::game::{Game, ROWS};
+use crate::packed::{MAX_WORD_LEN, WILDCARD};
+use crate::puzzle::Puzzle;
+
+pub type Grid = [[Option<char>; MAX_WORD_LEN]; ROWS];
+
+/// How far along a row of the grid is.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RowState {
+    Empty,
+    /// Some cells still empty.
+    Partial,
+    /// A word that's on the board.
+    Placed,
+    /// All five letters, but not a word the board can take, and why.
+    Rejected(String),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Editor {
+    grid: Grid,
+    /// Row and column of the cell being edited.
+    cursor: (usize, usize),
+    undo: Vec<Grid>,
+    redo: Vec<Grid>,
+}
+
+impl Editor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    /// The letters of `row`, if every cell has one.
+    pub fn word(&self, row: usize) -> Option<String> {
+        self.grid[row].iter().copied().collect()
+    }
+
+    /// Moves the cursor, stopping at the edges of the grid.
+    pub fn move_cursor(&mut self, rows: isize, cols: isize) {
+        let (row, col) = self.cursor;
+        self.cursor = (
+            row.saturating_add_signed(rows).min(ROWS - 1),
+            col.saturating_add_signed(cols).min(MAX_WORD_LEN - 1),
+        );
+    }
+
+    /// Puts `letter` under the cursor and moves on to the next cell in the row.
+    pub fn type_letter(&mut self, letter: char) {
+        let (row, col) = self.cursor;
+        let letter = letter.to_uppercase().next().unwrap_or(letter);
+        self.edit(|grid| grid[row][col] = Some(letter));
+        self.move_cursor(0, 1);
+    }
+
+    /// Empties the cell under the cursor, or the one before it if that's
+    /// already empty, and leaves the cursor on the emptied cell.
+    pub fn backspace(&mut self) {
+        let (row, col) = self.cursor;
+        if self.grid[row][col].is_none() {
+            self.move_cursor(0, -1);
+        }
+        self.delete();
+    }
+
+    /// Empties the cell under the cursor.
+    pub fn delete(&mut self) {
+        let (row, col) = self.cursor;
+        self.edit(|grid| grid[row][col] = None);
+    }
+
+    pub fn clear_row(&mut self, row: usize) {
+        self.edit(|grid| grid[row] = [None; MAX_WORD_LEN]);
+    }
+
+    /// Writes `words` into the rows from the top, as one edit.
+    pub fn fill(&mut self, words: &[String]) {
+        self.edit(|grid| {
+            for (cells, word) in grid.iter_mut().zip(words) {
+                for (cell, c) in cells.iter_mut().zip(word.chars()) {
+                    *cell = Some(c);
+                }
+            }
+        });
+    }
+
+    /// Goes back to the grid before the last edit, returning false if there's
+    /// nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(grid) = self.undo.pop() else {
+            return false;
+        };
+        self.redo.push(std::mem::replace(&mut self.grid, grid));
+        true
+    }
+
+    /// Puts back the last edit undone, returning false if there's none.
+    pub fn redo(&mut self) -> bool {
+        let Some(grid) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push(std::mem::replace(&mut self.grid, grid));
+        true
+    }
+
+    /// Applies `change`, keeping the grid before it for undo if it changed
+    /// anything. A new edit can't be redone past.
+    fn edit(&mut self, change: impl FnOnce(&mut Grid)) {
+        let before = self.grid;
+        change(&mut self.grid);
+        if self.grid != before {
+            self.undo.push(before);
+            self.redo.clear();
+        }
+    }
+
+    /// The grid as a game: each full row is placed, top to bottom, if it's a
+    /// word that fits what the rows above left of the bag.
+    pub fn game<'a>(
+        &self,
+        puzzle: &'a Puzzle,
+        dictionary: &'a dyn Dictionary,
+    ) -> (Game<'a>, [RowState; ROWS]) {
+        let mut game = Game::new(puzzle, dictionary);
+        let states = std::array::from_fn(|row| {
+            let filled = self.grid[row].iter().flatten().count();
+            match self.word(row) {
+                Some(word) => match game.place(row, &word) {
+                    Ok(()) => RowState::Placed,
+                    Err(e) => RowState::Rejected(e.to_string()),
+                },
+                None if filled == 0 => RowState::Empty,
+                None => RowState::Partial,
+            }
+        });
+        (game, states)
+    }
+
+    /// Each of the puzzle's tiles and how many are left once every letter in
+    /// the grid is taken out, full rows or not. Letters the bag runs out of
+    /// come from the wildcard, whose count goes below zero when there are
+    /// more of them than it can cover.
+    pub fn tiles_left(&self, puzzle: &Puzzle) -> Vec<(char, i32)> {
+        let mut left = [0i32; MAX_LETTERS + 1];
+        for (count, &n) in left.iter_mut().zip(&puzzle.letter_bag()) {
+            *count = n as i32;
+        }
+        for &c in self.grid.iter().flatten().flatten() {
+            match puzzle.alphabet.index_of(c) {
+                Some(i) if left[i] > 0 => left[i] -= 1,
+                _ => left[WILDCARD] -= 1,
+            }
+        }
+        puzzle
+            .letters
+            .iter()
+            .filter_map(|l| Some((l.ch, left[puzzle.letter_index(l.ch)?])))
+            .collect()
+    }
+}
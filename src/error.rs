@@ -0,0 +1,79 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// A dictionary line that can't be used as a word, when loading strictly.
+    BadLine(BadLine),
+    /// Malformed puzzle definitions and command line values.
+    Parse(String),
+    /// Well-formed input the game's rules don't allow, like a board with
+    /// tiles the puzzle doesn't have or a row that isn't a word.
+    Invalid(String),
+}
+
+/// A dictionary line that was skipped or rejected, and where it came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BadLine {
+    pub path: PathBuf,
+    /// 1-based, as editors count them.
+    pub line: usize,
+    pub text: String,
+    pub reason: BadLineReason,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BadLineReason {
+    /// Not a letter of the alphabet, nor an accented one that folds into it.
+    InvalidChar(char),
+}
+
+impl Error {
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Error::Io {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Error::BadLine(bad_line) => bad_line.fmt(f),
+            Error::Parse(message) | Error::Invalid(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BadLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: `{}`: ",
+            self.path.display(),
+            self.line,
+            self.text
+        )?;
+        match self.reason {
+            BadLineReason::InvalidChar(c) => write!(f, "invalid character {c:?}"),
+        }
+    }
+}
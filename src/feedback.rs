@@ -0,0 +1,239 @@
+//! Verdicts from the game on individual words, kept in a local append-only file
+//! and laid over the base word lists: rejected words are removed, accepted ones
+//! are added, and words the game scored as common are added as common words.
+//!
+//! Each line of the file is `<verdict> <WORD>`, e.g. `rejected AALII`. When a
+//! word has several verdicts the last one wins.
+
+use crate::alphabet::Alphabet;
+use crate::dictionary::{Diacritics, Dictionary, fits_bag, normalize_word};
+use crate::error::{Error, Result};
+use crate::puzzle::LetterBag;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub const DEFAULT_FEEDBACK_FILE: &str = "bongo-feedback.txt";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The game took the word.
+    Accepted,
+    /// The game refused the word.
+    Rejected,
+    /// The game took the word and gave it the common-word multiplier.
+    Common,
+}
+
+impl FromStr for Verdict {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "accepted" => Ok(Verdict::Accepted),
+            "rejected" => Ok(Verdict::Rejected),
+            "common" => Ok(Verdict::Common),
+            _ => Err(Error::Parse(format!(
+                "unknown verdict `{s}`, expected `accepted`, `rejected` or `common`"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Accepted => "accepted",
+            Verdict::Rejected => "rejected",
+            Verdict::Common => "common",
+        })
+    }
+}
+
+/// How applying feedback changed a word list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Overlay {
+    pub added: usize,
+    pub removed: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct FeedbackStore {
+    path: PathBuf,
+    verdicts: BTreeMap<String, Verdict>,
+}
+
+impl FeedbackStore {
+    /// Reads the store at `path`. A missing file is just an empty store.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut store = FeedbackStore {
+            path: path.to_path_buf(),
+            verdicts: BTreeMap::new(),
+        };
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(store),
+            Err(e) => return Err(Error::io(path, e)),
+        };
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| Error::io(path, e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let bad =
+                |reason: String| Error::Parse(format!("{}:{}: {reason}", path.display(), i + 1));
+            let (verdict, word) = line
+                .trim()
+                .split_once(' ')
+                .ok_or_else(|| bad(format!("expected `<verdict> <WORD>`, got `{line}`")))?;
+            let verdict = verdict.parse().map_err(|e: Error| bad(e.to_string()))?;
+            store.verdicts.insert(word.trim().to_string(), verdict);
+        }
+        Ok(store)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn verdict(&self, word: &str) -> Option<Verdict> {
+        self.verdicts.get(word).copied()
+    }
+
+    /// All words with a verdict, alphabetically.
+    pub fn verdicts(&self) -> impl Iterator<Item = (&str, Verdict)> {
+        self.verdicts.iter().map(|(w, &v)| (w.as_str(), v))
+    }
+
+    /// Records a verdict, appending it to the file straight away. The word is
+    /// normalized the way dictionaries of `alphabet` are.
+    pub fn record(&mut self, word: &str, verdict: Verdict, alphabet: &Alphabet) -> Result<()> {
+        let word = match normalize_word(word, Diacritics::Fold, alphabet) {
+            Ok(Some((word, _))) => word,
+            _ => return Err(Error::Parse(format!("`{word}` is not a word"))),
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| Error::io(&self.path, e))?;
+        writeln!(file, "{verdict} {word}").map_err(|e| Error::io(&self.path, e))?;
+        self.verdicts.insert(word, verdict);
+        Ok(())
+    }
+
+    /// Removes rejected words from `words` and appends accepted and common ones
+    /// it doesn't already have.
+    pub fn apply(&self, words: &mut Vec<String>) -> Overlay {
+        let before = words.len();
+        words.retain(|w| self.verdict(w) != Some(Verdict::Rejected));
+        let removed = before - words.len();
+
+        let present = words.iter().cloned().collect::<HashSet<_>>();
+        let allowed = self
+            .verdicts()
+            .filter(|&(w, v)| v != Verdict::Rejected && !present.contains(w))
+            .map(|(w, _)| w.to_string())
+            .collect::<Vec<_>>();
+        let added = allowed.len();
+        words.extend(allowed);
+        Overlay { added, removed }
+    }
+}
+
+/// A dictionary with a store's verdicts laid over it. Words with a `common`
+/// verdict count as common; accepted words keep the base's commonness, and
+/// are uncommon when the base doesn't have them.
+pub struct FeedbackDictionary {
+    base: Box<dyn Dictionary>,
+    store: FeedbackStore,
+    /// Accepted and common words `base` doesn't have.
+    added: Vec<String>,
+    /// How many of `base`'s words were rejected.
+    removed: usize,
+}
+
+impl FeedbackDictionary {
+    pub fn new(base: Box<dyn Dictionary>, store: FeedbackStore) -> Self {
+        let added = store
+            .verdicts()
+            .filter(|&(w, v)| v != Verdict::Rejected && !base.contains(w))
+            .map(|(w, _)| w.to_string())
+            .collect();
+        let removed = store
+            .verdicts()
+            .filter(|&(w, v)| v == Verdict::Rejected && base.contains(w))
+            .count();
+        FeedbackDictionary {
+            base,
+            store,
+            added,
+            removed,
+        }
+    }
+
+    pub fn overlay(&self) -> Overlay {
+        Overlay {
+            added: self.added.len(),
+            removed: self.removed,
+        }
+    }
+
+    fn kept(&self, word: &str) -> bool {
+        self.store.verdict(word) != Some(Verdict::Rejected)
+    }
+}
+
+impl Dictionary for FeedbackDictionary {
+    fn contains(&self, word: &str) -> bool {
+        match self.store.verdict(word) {
+            Some(Verdict::Accepted | Verdict::Common) => true,
+            Some(Verdict::Rejected) => false,
+            None => self.base.contains(word),
+        }
+    }
+
+    fn is_common(&self, word: &str) -> bool {
+        match self.store.verdict(word) {
+            Some(Verdict::Common) => true,
+            Some(Verdict::Accepted) => self.base.contains(word) && self.base.is_common(word),
+            Some(Verdict::Rejected) => false,
+            None => self.base.is_common(word),
+        }
+    }
+
+    fn words(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(
+            self.base
+                .words()
+                .filter(|w| self.kept(w))
+                .chain(self.added.iter().cloned()),
+        )
+    }
+
+    fn len(&self) -> usize {
+        self.base.len() - self.removed + self.added.len()
+    }
+
+    fn words_of_length(&self, len: usize) -> Box<dyn Iterator<Item = String> + '_> {
+        let added = self.added.iter().filter(move |w| w.chars().count() == len);
+        Box::new(
+            self.base
+                .words_of_length(len)
+                .filter(|w| self.kept(w))
+                .chain(added.cloned()),
+        )
+    }
+
+    fn words_fitting(&self, bag: &LetterBag, alphabet: &Alphabet) -> Vec<String> {
+        let mut words = self.base.words_fitting(bag, alphabet);
+        words.retain(|w| self.kept(w));
+        let added = self.added.iter().filter(|w| fits_bag(w, bag, alphabet));
+        words.extend(added.cloned());
+        words
+    }
+}
@@ -0,0 +1,208 @@
+//! A board being played by hand: words go into rows one at a time, checked
+//! against the dictionary and what's left of the bag, and the board is scored
+//! as it fills up.
+
+use crate::dictionary::Dictionary;
+use crate::error::{Error, Result};
+use crate::packed::{MAX_WORD_LEN, PackedWord, WILDCARD};
+use crate::puzzle::{LetterBag, Puzzle};
+use crate::solver::{Board, Bonus, best_completion, remove_word_from_bag, score_board, score_word};
+use std::sync::atomic::AtomicBool;
+
+pub const ROWS: usize = 5;
+
+pub struct Game<'a> {
+    pub puzzle: &'a Puzzle,
+    dictionary: &'a dyn Dictionary,
+    rows: [Option<PackedWord>; ROWS],
+}
+
+impl<'a> Game<'a> {
+    pub fn new(puzzle: &'a Puzzle, dictionary: &'a dyn Dictionary) -> Self {
+        Game {
+            puzzle,
+            dictionary,
+            rows: Default::default(),
+        }
+    }
+
+    pub fn rows(&self) -> &[Option<PackedWord>; ROWS] {
+        &self.rows
+    }
+
+    /// The word in `row`, if there is one.
+    pub fn word(&self, row: usize) -> Option<&str> {
+        self.rows[row].as_ref().map(|w| w.word.as_str())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.rows.iter().all(Option::is_some)
+    }
+
+    /// The first row without a word.
+    pub fn next_empty_row(&self) -> Option<usize> {
+        self.rows.iter().position(Option::is_none)
+    }
+
+    /// The tiles not on the board, wildcard included.
+    pub fn remaining(&self) -> LetterBag {
+        self.remaining_without(None)
+    }
+
+    fn remaining_without(&self, skip: Option<usize>) -> LetterBag {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|&(row, _)| Some(row) != skip)
+            .filter_map(|(_, word)| word.as_ref())
+            .fold(self.puzzle.letter_bag(), |bag, word| {
+                remove_word_from_bag(word, &bag).expect("placed words fit the bag")
+            })
+    }
+
+    /// Puts `word` in `row`, replacing what was there. The word has to be in
+    /// the dictionary and spelled from the tiles left, with the wildcard
+    /// standing in for at most one missing letter.
+    pub fn place(&mut self, row: usize, word: &str) -> Result<()> {
+        if row >= ROWS {
+            return Err(Error::Invalid(format!("there are only {ROWS} rows")));
+        }
+        let word = word.trim().to_uppercase();
+        if word.chars().count() != MAX_WORD_LEN {
+            return Err(Error::Invalid(format!(
+                "`{word}` isn't {MAX_WORD_LEN} letters long"
+            )));
+        }
+        let mut bag = self.remaining_without(Some(row));
+        let mut wildcard = None;
+        for c in word.chars() {
+            let i = self
+                .puzzle
+                .alphabet
+                .index_of(c)
+                .ok_or_else(|| Error::Invalid(format!("{c:?} isn't in the puzzle")))?;
+            if bag[i] > 0 {
+                bag[i] -= 1;
+            } else if bag[WILDCARD] > 0 && wildcard.is_none() {
+                bag[WILDCARD] -= 1;
+                wildcard = Some(c);
+            } else {
+                return Err(Error::Invalid(format!("no {c} tiles left for `{word}`")));
+            }
+        }
+        if !self.dictionary.contains(&word) {
+            return Err(Error::Invalid(format!("`{word}` isn't in the dictionary")));
+        }
+        let mut word = PackedWord::new(&word, wildcard, self.puzzle);
+        word.common = self.dictionary.is_common(&word.word);
+        self.rows[row] = Some(word);
+        self.repack();
+        Ok(())
+    }
+
+    /// Takes the word out of `row`, putting its tiles back in the bag.
+    pub fn clear(&mut self, row: usize) -> Option<String> {
+        let word = self.rows.get_mut(row)?.take()?;
+        self.repack();
+        Some(word.word)
+    }
+
+    /// Works out again which word needs the wildcard, going down the rows, so
+    /// a word stops needing it once another word gives a tile back.
+    fn repack(&mut self) {
+        let mut bag = self.puzzle.letter_bag();
+        for word in self.rows.iter_mut().flatten() {
+            let mut wildcard = None;
+            for &letter in word.letters() {
+                if bag[letter as usize] > 0 {
+                    bag[letter as usize] -= 1;
+                } else {
+                    wildcard = Some(letter);
+                }
+            }
+            word.wildcard = wildcard;
+        }
+    }
+
+    /// The letters along the bonus path, once every row on it has a word.
+    pub fn bonus_word(&self) -> Option<String> {
+        self.puzzle
+            .bonus_path
+            .iter()
+            .map(|&(row, col)| self.rows[row].as_ref()?.word.chars().nth(col))
+            .collect()
+    }
+
+    /// Whether the bonus path spells a word.
+    pub fn bonus_word_used(&self) -> bool {
+        self.bonus_word()
+            .is_some_and(|word| self.dictionary.contains(&word))
+    }
+
+    /// What the bonus path spells, for scoring.
+    pub fn bonus(&self) -> Bonus {
+        match self.bonus_word() {
+            Some(word) if self.dictionary.contains(&word) => Bonus::Spelled {
+                common: self.dictionary.is_common(&word),
+            },
+            _ => Bonus::Missed,
+        }
+    }
+
+    /// Cell the wildcard stands in, taken as the first of its letter's cells
+    /// in the row that needed it. `score_board` picks the best one once the
+    /// board is complete.
+    fn wildcard_cell(&self) -> Option<(usize, usize)> {
+        self.rows.iter().enumerate().find_map(|(row, word)| {
+            let word = word.as_ref()?;
+            Some((row, word.position_of(word.wildcard?)?))
+        })
+    }
+
+    /// Each row's score on its own.
+    pub fn row_scores(&self) -> [Option<u32>; ROWS] {
+        let wildcard = self.wildcard_cell();
+        std::array::from_fn(|row| {
+            let word = self.rows[row].as_ref()?;
+            Some(score_word(row, word, wildcard, self.puzzle))
+        })
+    }
+
+    /// The board's score so far: `score_board` once it's complete, and the
+    /// sum of the rows before that.
+    pub fn score(&self) -> u32 {
+        match self.board() {
+            Some(board) => score_board(&board, self.bonus(), self.puzzle),
+            None => self.row_scores().iter().flatten().sum(),
+        }
+    }
+
+    /// The rows as a board, once every row has a word.
+    pub fn board(&self) -> Option<Board<'_>> {
+        self.rows.iter().map(Option::as_ref).collect()
+    }
+
+    /// The words of the best board that keeps the rows filled so far, and its
+    /// score, searching the rows and bonus words `split_valid_words` gives.
+    /// `None` if no such board spells a bonus word.
+    pub fn best_completion(
+        &self,
+        valid_words: &[&PackedWord],
+        bonus_words: &[&PackedWord],
+    ) -> Option<(Vec<String>, u32)> {
+        self.best_completion_unless(valid_words, bonus_words, &AtomicBool::new(false))
+    }
+
+    /// `best_completion`, giving up with `None` once `cancelled` is set.
+    pub fn best_completion_unless(
+        &self,
+        valid_words: &[&PackedWord],
+        bonus_words: &[&PackedWord],
+        cancelled: &AtomicBool,
+    ) -> Option<(Vec<String>, u32)> {
+        let fixed = std::array::from_fn(|row| self.rows[row].as_ref());
+        let (board, score) =
+            best_completion(&fixed, valid_words, bonus_words, self.puzzle, cancelled);
+        (score > 0).then(|| (board.iter().map(|w| w.word.clone()).collect(), score))
+    }
+}
@@ -0,0 +1,219 @@
+//! The games someone has played, one JSON object per line of a local file,
+//! and the streaks and averages worked out from them.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where the history is kept unless a path is given.
+pub const DEFAULT_PATH: &str = "bongo-history.jsonl";
+
+/// A finished game.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// The day it was played, as `YYYY-MM-DD` in UTC.
+    pub date: String,
+    /// The puzzle's share code.
+    pub code: String,
+    /// The row words, with an empty string for a row left empty.
+    pub board: Vec<String>,
+    pub score: u32,
+    pub seconds: u64,
+    /// The best score the solver found, or 0 if it found none.
+    pub optimum: u32,
+}
+
+impl Entry {
+    /// The score as a percentage of the optimum.
+    pub fn percent(&self) -> Option<f64> {
+        (self.optimum > 0).then(|| 100.0 * self.score as f64 / self.optimum as f64)
+    }
+}
+
+/// Reads every game in the history at `path`, which is empty if there's no
+/// file yet.
+pub fn load(path: &Path) -> Result<Vec<Entry>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(Error::io(path, e)),
+    };
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| Error::Parse(format!("{}:{}: {e}", path.display(), i + 1)))
+        })
+        .collect()
+}
+
+/// Adds a game to the end of the history at `path`, creating it if needed.
+pub fn append(path: &Path, entry: &Entry) -> Result<()> {
+    let mut line = serde_json::to_string(entry).expect("entries serialize");
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| Error::io(path, e))
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The date `days` after 1970-01-01, as `YYYY-MM-DD`.
+pub fn civil_from_days(days: i64) -> String {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Days since 1970-01-01 for a `YYYY-MM-DD` date.
+pub fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts
+        .next()?
+        .parse()
+        .ok()
+        .filter(|m| (1..=12).contains(m))?;
+    let day = parts
+        .next()?
+        .parse()
+        .ok()
+        .filter(|d| (1..=days_in_month(year, month)).contains(d))?;
+    Some(days_from_civil(year, month, day))
+}
+
+/// How long `month` is in `year`, leap days included.
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Today's date in UTC, as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    civil_from_days((seconds / 86400) as i64)
+}
+
+/// A summary of a history.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub games: usize,
+    /// Different days with at least one game.
+    pub days: usize,
+    /// Days in a row played up to today, or up to yesterday if today's game
+    /// is still to come.
+    pub current_streak: usize,
+    pub best_streak: usize,
+    pub average_score: f64,
+    /// Over the games with a known optimum.
+    pub average_percent: Option<f64>,
+    pub average_seconds: f64,
+    /// The game closest to its optimum, or with the best score if no
+    /// optimum is known.
+    pub best: Option<Entry>,
+}
+
+/// Works out the streaks and averages of `entries`, taking `today` as the
+/// current day.
+pub fn stats(entries: &[Entry], today: &str) -> Stats {
+    if entries.is_empty() {
+        return Stats::default();
+    }
+    let days = entries
+        .iter()
+        .filter_map(|e| parse_date(&e.date))
+        .collect::<BTreeSet<_>>();
+    let mut best_streak = 0;
+    let mut streak = 0;
+    let mut previous = None;
+    for &day in &days {
+        streak = if previous == Some(day - 1) {
+            streak + 1
+        } else {
+            1
+        };
+        best_streak = best_streak.max(streak);
+        previous = Some(day);
+    }
+    // Counted back from today, so games dated after it can't keep a streak
+    // alive
+    let current_streak = parse_date(today).map_or(0, |today| {
+        let mut current = 0;
+        let mut next = None;
+        for &day in days.range(..=today).rev() {
+            let follows = match next {
+                None => today - day <= 1,
+                Some(next) => next - day == 1,
+            };
+            if !follows {
+                break;
+            }
+            current += 1;
+            next = Some(day);
+        }
+        current
+    });
+
+    let games = entries.len() as f64;
+    let percents = entries
+        .iter()
+        .filter_map(Entry::percent)
+        .collect::<Vec<_>>();
+    let best = entries
+        .iter()
+        .max_by(|a, b| {
+            let key = |e: &Entry| (e.percent().unwrap_or(0.0), e.score);
+            key(a).partial_cmp(&key(b)).expect("percentages aren't NaN")
+        })
+        .cloned();
+    Stats {
+        games: entries.len(),
+        days: days.len(),
+        current_streak,
+        best_streak,
+        average_score: entries.iter().map(|e| e.score as f64).sum::<f64>() / games,
+        average_percent: (!percents.is_empty())
+            .then(|| percents.iter().sum::<f64>() / percents.len() as f64),
+        average_seconds: entries.iter().map(|e| e.seconds as f64).sum::<f64>() / games,
+        best,
+    }
+}
+
+/// The best score in `entries` for the puzzle with share code `code`.
+pub fn personal_best(entries: &[Entry], code: &str) -> Option<u32> {
+    entries
+        .iter()
+        .filter(|e| e.code == code)
+        .map(|e| e.score)
+        .max()
+}
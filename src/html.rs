@@ -0,0 +1,185 @@
+//! A solve written up as one self-contained HTML page for post-mortems: the
+//! puzzle, the best board and the runners-up drawn with their score
+//! breakdowns, the words each row could have used and the solver's stats. The
+//! styles and boards are inline, so the file works offline and can be attached
+//! as is.
+
+use crate::packed::PackedWord;
+use crate::puzzle::Puzzle;
+use crate::render::{board_svg, escape};
+use crate::report::{BoardReport, SolveReport};
+use crate::solver::Board;
+use std::fmt::Write;
+
+/// How many of each row's candidate words to list before just counting them.
+const MAX_ROW_WORDS: usize = 25;
+
+const STYLE: &str = "
+body { font-family: Helvetica, Arial, sans-serif; color: #2b2b2b; background: #fbfaf7;
+       max-width: 1000px; margin: 2em auto; padding: 0 1em; }
+h1 { margin-bottom: 0.2em; }
+section { margin: 2em 0; }
+pre { background: #f4f1ea; padding: 1em; border-radius: 8px; overflow-x: auto; }
+table { border-collapse: collapse; margin: 0.5em 0; }
+th, td { padding: 0.25em 0.75em; text-align: left; border-bottom: 1px solid #e2ded5; }
+td.score, th.score { text-align: right; font-variant-numeric: tabular-nums; }
+tr.total td { font-weight: bold; border-bottom: none; }
+.board { display: flex; flex-wrap: wrap; gap: 2em; align-items: flex-start; }
+.rows { display: flex; flex-wrap: wrap; gap: 1.5em; }
+.muted { color: #7a7a7a; }
+";
+
+/// The HTML page for a solve. `boards` are the boards `report` was made from,
+/// best first, and `row_candidates` the words the solver could place in rows.
+pub fn html_report(
+    report: &SolveReport,
+    boards: &[(Board, u32)],
+    row_candidates: &[&PackedWord],
+    puzzle: &Puzzle,
+) -> String {
+    let id = escape(&report.puzzle_id);
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Bongo solve report: {id}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>{id}</h1>\n"
+    );
+    let _ = writeln!(
+        html,
+        "<p class=\"muted\">Solved with {} in {} ms</p>",
+        escape(&report.stats.strategy),
+        report.stats.elapsed_ms
+    );
+
+    let _ = writeln!(
+        html,
+        "<section>\n<h2>Puzzle</h2>\n<pre>{}</pre>\n</section>",
+        escape(&puzzle.to_string())
+    );
+
+    html.push_str("<section>\n<h2>Best board</h2>\n");
+    match (boards.first(), report.boards.first()) {
+        (Some((board, _)), Some(breakdown)) => board_section(&mut html, board, breakdown, puzzle),
+        _ => html.push_str("<p>No valid board found.</p>\n"),
+    }
+    html.push_str("</section>\n");
+
+    if boards.len() > 1 {
+        html.push_str("<section>\n<h2>Alternatives</h2>\n");
+        for ((board, _), breakdown) in boards.iter().zip(&report.boards).skip(1) {
+            let _ = writeln!(
+                html,
+                "<h3>#{} &middot; {}</h3>",
+                breakdown.rank, breakdown.total
+            );
+            board_section(&mut html, board, breakdown, puzzle);
+        }
+        html.push_str("</section>\n");
+    }
+
+    row_words_section(&mut html, row_candidates, puzzle);
+    stats_section(&mut html, report);
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// A board drawn next to its score breakdown.
+fn board_section(html: &mut String, board: &[&PackedWord], report: &BoardReport, puzzle: &Puzzle) {
+    html.push_str("<div class=\"board\">\n");
+    html.push_str(&board_svg(board, report.bonus(), puzzle));
+    html.push_str("<table>\n<tr><th></th><th>Word</th><th class=\"score\">Score</th></tr>\n");
+    for (i, row) in report.rows.iter().enumerate() {
+        let _ = writeln!(
+            html,
+            "<tr><td>Row {}</td><td>{}</td><td class=\"score\">{}</td></tr>",
+            i + 1,
+            escape(&row.word),
+            row.score
+        );
+    }
+    let _ = writeln!(
+        html,
+        "<tr><td>Bonus</td><td>{}</td><td class=\"score\">{}</td></tr>",
+        escape(&report.bonus_word),
+        report
+            .bonus_score
+            .map_or_else(|| "-".to_string(), |s| s.to_string())
+    );
+    if let Some(wildcard) = &report.wildcard {
+        let _ = writeln!(
+            html,
+            "<tr><td>Wildcard</td><td>{} at row {}, column {}</td><td></td></tr>",
+            wildcard.letter,
+            wildcard.row + 1,
+            wildcard.col + 1
+        );
+    }
+    let _ = writeln!(
+        html,
+        "<tr class=\"total\"><td>Total</td><td></td><td class=\"score\">{}</td></tr>\n</table>\n</div>",
+        report.total
+    );
+}
+
+/// Each row's candidate words, best scoring there first. Words marked `*` need
+/// the wildcard.
+fn row_words_section(html: &mut String, row_candidates: &[&PackedWord], puzzle: &Puzzle) {
+    html.push_str(
+        "<section>\n<h2>Formable words by row</h2>\n\
+         <p class=\"muted\">Scored on their own, before the wildcard is placed. \
+         Words marked * need the wildcard.</p>\n<div class=\"rows\">\n",
+    );
+    for row in 0..5 {
+        let mut words = row_candidates
+            .iter()
+            .map(|w| (w, puzzle.word_score(w.row_totals[row], w.common)))
+            .collect::<Vec<_>>();
+        words.sort_by(|(a, a_score), (b, b_score)| b_score.cmp(a_score).then(a.word.cmp(&b.word)));
+        let _ = writeln!(
+            html,
+            "<table>\n<tr><th>Row {}</th><th class=\"score\">{}</th></tr>",
+            row + 1,
+            words.len()
+        );
+        for (word, score) in words.iter().take(MAX_ROW_WORDS) {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}{}</td><td class=\"score\">{score}</td></tr>",
+                escape(&word.word),
+                if word.wildcard.is_some() { "*" } else { "" }
+            );
+        }
+        if words.len() > MAX_ROW_WORDS {
+            let _ = writeln!(
+                html,
+                "<tr><td class=\"muted\" colspan=\"2\">and {} more</td></tr>",
+                words.len() - MAX_ROW_WORDS
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</div>\n</section>\n");
+}
+
+fn stats_section(html: &mut String, report: &SolveReport) {
+    let stats = &report.stats;
+    html.push_str("<section>\n<h2>Solver statistics</h2>\n<table>\n");
+    for (name, value) in [
+        ("Strategy", escape(&stats.strategy)),
+        (
+            "Dictionary words fitting the bag",
+            stats.dictionary_words.to_string(),
+        ),
+        ("Bonus word candidates", stats.bonus_candidates.to_string()),
+        ("Row candidates", stats.row_candidates.to_string()),
+        ("Boards found", stats.boards_found.to_string()),
+        ("Solve time (ms)", stats.elapsed_ms.to_string()),
+    ] {
+        let _ = writeln!(
+            html,
+            "<tr><td>{name}</td><td class=\"score\">{value}</td></tr>"
+        );
+    }
+    html.push_str("</table>\n</section>\n");
+}
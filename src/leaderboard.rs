@@ -0,0 +1,267 @@
+//! A team leaderboard kept as JSON files in a shared directory, so it works
+//! from a network drive or synced folder with no server.
+//!
+//! Each day has its own directory, named by date, holding `puzzle.json` with
+//! the day's puzzle code and optimum and one `<player>.json` per player.
+//! Every player only ever writes their own file, so submissions from several
+//! machines don't step on each other. A player who submits again replaces
+//! their board for the day.
+//!
+//! ```text
+//! team/
+//!   2026-10-18/
+//!     puzzle.json
+//!     alice.json
+//!     bob.json
+//! ```
+
+use crate::code::encode;
+use crate::dictionary::Dictionary;
+use crate::error::{Error, Result};
+use crate::history::{civil_from_days, parse_date};
+use crate::puzzle::Puzzle;
+use crate::share::SharedBoard;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const PUZZLE_FILE: &str = "puzzle.json";
+
+/// The puzzle everyone plays on a given day.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayPuzzle {
+    /// The puzzle's share code.
+    pub code: String,
+    /// The best score, once someone has solved for it.
+    pub optimum: Option<u32>,
+}
+
+/// A player's board for a day, checked and scored when it was submitted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub player: String,
+    pub date: String,
+    pub board: Vec<String>,
+    pub score: u32,
+}
+
+/// A player's place on a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DailyRank {
+    /// Players with the same score share a rank.
+    pub rank: usize,
+    pub player: String,
+    pub score: u32,
+    /// How far the score is from the day's optimum, if it's known.
+    pub gap: Option<u32>,
+}
+
+/// A player's scores over the week.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeeklyTotal {
+    pub rank: usize,
+    pub player: String,
+    pub total: u32,
+    /// How many of the week's days they played.
+    pub days: usize,
+}
+
+pub struct Leaderboard {
+    dir: PathBuf,
+}
+
+/// Player names become file names, so they're kept to letters, digits, `-`,
+/// `_` and `.`, and can't start with a dot.
+fn check_player(player: &str) -> Result<()> {
+    let allowed = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '.');
+    if player.is_empty() || player.starts_with('.') || !player.chars().all(allowed) {
+        return Err(Error::Invalid(format!(
+            "player names can only have letters, digits, `-`, `_` and `.`, not `{player}`"
+        )));
+    }
+    if player == "puzzle" {
+        return Err(Error::Invalid(
+            "`puzzle` can't be a player name".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn check_date(date: &str) -> Result<i64> {
+    parse_date(date).ok_or_else(|| Error::Parse(format!("bad date `{date}`, expected YYYY-MM-DD")))
+}
+
+/// Ranks scores from best to worst, ties sharing a rank.
+fn ranks<T>(mut items: Vec<T>, score: impl Fn(&T) -> u32) -> Vec<(usize, T)> {
+    items.sort_by_key(|item| std::cmp::Reverse(score(item)));
+    let mut ranked: Vec<(usize, T)> = Vec::with_capacity(items.len());
+    for (i, item) in items.into_iter().enumerate() {
+        let rank = match ranked.last() {
+            Some((rank, last)) if score(last) == score(&item) => *rank,
+            _ => i + 1,
+        };
+        ranked.push((rank, item));
+    }
+    ranked
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(Error::io(path, e)),
+    };
+    serde_json::from_str(&text)
+        .map(Some)
+        .map_err(|e| Error::Parse(format!("{}: {e}", path.display())))
+}
+
+/// Writes to a temporary file first, so readers never see half a file.
+fn write_json(path: &Path, value: &impl Serialize) -> Result<()> {
+    let dir = path
+        .parent()
+        .expect("leaderboard files are in a day directory");
+    std::fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let json = serde_json::to_string_pretty(value).expect("leaderboard files serialize");
+    std::fs::write(&temp, json).map_err(|e| Error::io(&temp, e))?;
+    std::fs::rename(&temp, path).map_err(|e| Error::io(path, e))
+}
+
+impl Leaderboard {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Leaderboard { dir: dir.into() }
+    }
+
+    /// The directory for `date`, named in full so `2026-1-5` and
+    /// `2026-01-05` are the same day.
+    fn day_dir(&self, date: &str) -> Result<PathBuf> {
+        Ok(self.dir.join(civil_from_days(check_date(date)?)))
+    }
+
+    /// The day's puzzle, if anyone has submitted for it yet.
+    pub fn day_puzzle(&self, date: &str) -> Result<Option<DayPuzzle>> {
+        read_json(&self.day_dir(date)?.join(PUZZLE_FILE))
+    }
+
+    /// Records the day's optimum.
+    pub fn set_optimum(&self, date: &str, optimum: u32) -> Result<()> {
+        let mut day = self
+            .day_puzzle(date)?
+            .ok_or_else(|| Error::Invalid(format!("there's no puzzle for {date} yet")))?;
+        day.optimum = Some(optimum);
+        write_json(&self.day_dir(date)?.join(PUZZLE_FILE), &day)
+    }
+
+    /// Checks `board` against `puzzle` and `dictionary`, scores it, and saves
+    /// it as `player`'s board for `date`. The first submission of a day sets
+    /// its puzzle; later ones have to be for the same puzzle.
+    pub fn submit(
+        &self,
+        player: &str,
+        date: &str,
+        puzzle: &Puzzle,
+        board: &SharedBoard,
+        dictionary: &dyn Dictionary,
+    ) -> Result<Submission> {
+        check_player(player)?;
+        let code = encode(puzzle);
+        let day = self.day_puzzle(date)?;
+        if let Some(day) = &day
+            && day.code != code
+        {
+            return Err(Error::Invalid(format!(
+                "{date} is being played with puzzle {}, not this one",
+                day.code
+            )));
+        }
+        let checked = board.check(puzzle, dictionary)?;
+        if day.is_none() {
+            let day = DayPuzzle {
+                code,
+                optimum: None,
+            };
+            write_json(&self.day_dir(date)?.join(PUZZLE_FILE), &day)?;
+        }
+        let submission = Submission {
+            player: player.to_string(),
+            date: civil_from_days(check_date(date)?),
+            board: board.rows.clone(),
+            score: checked.score,
+        };
+        write_json(
+            &self.day_dir(date)?.join(format!("{player}.json")),
+            &submission,
+        )?;
+        Ok(submission)
+    }
+
+    /// Every board submitted for `date`, in no particular order.
+    pub fn submissions(&self, date: &str) -> Result<Vec<Submission>> {
+        let dir = self.day_dir(date)?;
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(Error::io(&dir, e)),
+        };
+        let mut submissions = vec![];
+        for entry in entries {
+            let path = entry.map_err(|e| Error::io(&dir, e))?.path();
+            let is_player = path.extension().is_some_and(|e| e == "json")
+                && path.file_name().is_some_and(|n| n != PUZZLE_FILE);
+            if is_player && let Some(submission) = read_json(&path)? {
+                submissions.push(submission);
+            }
+        }
+        Ok(submissions)
+    }
+
+    /// The day's players from best to worst, with their gaps to the optimum.
+    pub fn daily(&self, date: &str) -> Result<Vec<DailyRank>> {
+        let optimum = self.day_puzzle(date)?.and_then(|day| day.optimum);
+        let mut submissions = self.submissions(date)?;
+        submissions.sort_by(|a, b| a.player.cmp(&b.player));
+        Ok(ranks(submissions, |s| s.score)
+            .into_iter()
+            .map(|(rank, s)| DailyRank {
+                rank,
+                gap: optimum.map(|optimum| optimum.saturating_sub(s.score)),
+                player: s.player,
+                score: s.score,
+            })
+            .collect())
+    }
+
+    /// The dates of the week, Monday to Sunday, that `date` falls in.
+    pub fn week_of(date: &str) -> Result<Vec<String>> {
+        let day = check_date(date)?;
+        // 1970-01-01 was a Thursday
+        let monday = day - (day + 3).rem_euclid(7);
+        Ok((monday..monday + 7).map(civil_from_days).collect())
+    }
+
+    /// Each player's total over the week `date` falls in, best first.
+    pub fn weekly(&self, date: &str) -> Result<Vec<WeeklyTotal>> {
+        let mut totals = BTreeMap::<String, (u32, usize)>::new();
+        for day in Self::week_of(date)? {
+            for submission in self.submissions(&day)? {
+                let total = totals.entry(submission.player).or_default();
+                total.0 += submission.score;
+                total.1 += 1;
+            }
+        }
+        Ok(
+            ranks(totals.into_iter().collect(), |(_, (total, _))| *total)
+                .into_iter()
+                .map(|(rank, (player, (total, days)))| WeeklyTotal {
+                    rank,
+                    player,
+                    total,
+                    days,
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,21 @@
+pub mod alphabet;
+pub mod brute_force;
+pub mod code;
+pub mod dictionary;
+pub mod editor;
+pub mod error;
+pub mod feedback;
+pub mod game;
+pub mod history;
+pub mod html;
+pub mod leaderboard;
+pub mod packed;
+pub mod progress;
+pub mod puzzle;
+pub mod render;
+pub mod report;
+pub mod scoring;
+pub mod share;
+pub mod solver;
+
+pub use error::{Error, Result};
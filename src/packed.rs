@@ -0,0 +1,82 @@
+use crate::alphabet::MAX_LETTERS;
+use crate::puzzle::Puzzle;
+
+pub const MAX_WORD_LEN: usize = 5;
+/// Bag index of the wildcard tile.
+pub const WILDCARD: usize = MAX_LETTERS;
+
+/// A word pre-encoded for the search: alphabet indices of its letters, a
+/// letter-count vector to subtract from the bag, and every cell's score already
+/// multiplied out against the puzzle's scoring rules, so nothing in the hot
+/// loop touches chars or floats.
+#[derive(Clone, Debug)]
+pub struct PackedWord {
+    pub word: String,
+    pub letters: [u8; MAX_WORD_LEN],
+    pub len: usize,
+    /// How many of each of the puzzle's letters the word has, by alphabet
+    /// index.
+    pub counts: Vec<u8>,
+    /// Letter index the wildcard stands in for, if the word needs it.
+    pub wildcard: Option<u8>,
+    /// Whether the game counts the word as common, which is what earns it the
+    /// puzzle's multiplier.
+    pub common: bool,
+    /// `Puzzle::cell_score` of each letter in each row.
+    pub cell_scores: [[u32; MAX_WORD_LEN]; 5],
+    /// Sum of `cell_scores[row]`.
+    pub row_totals: [u32; 5],
+}
+
+impl PackedWord {
+    /// # Panics
+    ///
+    /// If the word is longer than `MAX_WORD_LEN` or has letters outside the
+    /// puzzle's alphabet and `*`; `find_valid_words` only packs words that pass
+    /// both.
+    ///
+    /// The word counts as common, like every word of the plain lists the game
+    /// is played with; set `common` for words from other dictionaries.
+    pub fn new(word: &str, wildcard: Option<char>, puzzle: &Puzzle) -> Self {
+        let len = word.chars().count();
+        assert!(len <= MAX_WORD_LEN);
+        let index = |c| {
+            puzzle
+                .letter_index(c)
+                .unwrap_or_else(|| panic!("{c:?} is not in the alphabet"))
+        };
+        let mut letters = [0; MAX_WORD_LEN];
+        let mut counts = vec![0; puzzle.alphabet.len()];
+        let mut cell_scores = [[0; MAX_WORD_LEN]; 5];
+        for (col, ch) in word.chars().enumerate() {
+            let idx = index(ch);
+            letters[col] = idx as u8;
+            // `*` stands for the wildcard tile, which isn't a letter
+            if let Some(count) = counts.get_mut(idx) {
+                *count += 1;
+            }
+            for (row, scores) in cell_scores.iter_mut().enumerate() {
+                scores[col] = puzzle.cell_score(row, col, ch);
+            }
+        }
+        PackedWord {
+            word: word.to_string(),
+            letters,
+            len,
+            counts,
+            wildcard: wildcard.map(|c| index(c) as u8),
+            common: true,
+            cell_scores,
+            row_totals: cell_scores.map(|scores| scores.iter().sum()),
+        }
+    }
+
+    pub fn letters(&self) -> &[u8] {
+        &self.letters[..self.len]
+    }
+
+    /// Column of the first occurrence of `letter`, if any.
+    pub fn position_of(&self, letter: u8) -> Option<usize> {
+        self.letters().iter().position(|&l| l == letter)
+    }
+}
@@ -0,0 +1,153 @@
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Redraw the bar at most this often, so workers aren't stuck writing to stderr.
+const REDRAW_INTERVAL_MS: u64 = 100;
+const BAR_LEN: usize = 40;
+
+/// A snapshot of how far along a solve is.
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    pub done: usize,
+    pub total: usize,
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.done as f64 / self.total as f64
+        }
+    }
+
+    /// Items completed per second so far.
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.done as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Estimated time left, assuming the remaining items go at the current rate.
+    pub fn eta(&self) -> Option<Duration> {
+        let rate = self.throughput();
+        if self.done == 0 || rate <= 0.0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.done) as f64;
+        Some(Duration::from_secs_f64(remaining / rate))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.done >= self.total
+    }
+}
+
+/// Lock-free completion counter shared by the worker threads.
+pub struct ProgressCounter {
+    done: AtomicUsize,
+    total: usize,
+    start: Instant,
+}
+
+impl ProgressCounter {
+    pub fn new(total: usize) -> Self {
+        ProgressCounter {
+            done: AtomicUsize::new(0),
+            total,
+            start: Instant::now(),
+        }
+    }
+
+    /// Marks one more item as done and returns the resulting snapshot.
+    pub fn tick(&self) -> Progress {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        Progress {
+            done,
+            total: self.total,
+            elapsed: self.start.elapsed(),
+        }
+    }
+}
+
+/// Draws progress snapshots as a single updating line on stderr.
+///
+/// Disabled when asked to be quiet or when stderr is not a terminal, so piped
+/// or redirected runs only ever see the results.
+pub struct ProgressBar {
+    enabled: bool,
+    start: Instant,
+    last_draw_ms: AtomicU64,
+}
+
+impl ProgressBar {
+    pub fn new(quiet: bool) -> Self {
+        ProgressBar {
+            enabled: !quiet && std::io::stderr().is_terminal(),
+            start: Instant::now(),
+            last_draw_ms: AtomicU64::new(0),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn update(&self, progress: &Progress) {
+        if !self.enabled {
+            return;
+        }
+        // Only one worker gets to draw per interval; the final update always draws.
+        let now_ms = self.start.elapsed().as_millis() as u64;
+        let last = self.last_draw_ms.load(Ordering::Relaxed);
+        if !progress.is_finished()
+            && (now_ms < last + REDRAW_INTERVAL_MS
+                || self
+                    .last_draw_ms
+                    .compare_exchange(last, now_ms, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_err())
+        {
+            return;
+        }
+
+        let filled = ((progress.fraction() * BAR_LEN as f64).round() as usize).min(BAR_LEN);
+        let bar = "#".repeat(filled) + &"-".repeat(BAR_LEN - filled);
+        let eta = match progress.eta() {
+            Some(eta) => format_duration(eta),
+            None => "--:--".to_string(),
+        };
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(
+            stderr,
+            "\r[{}] {:.2}% ({} / {}) {:.1}/s ETA {}",
+            bar,
+            progress.fraction() * 100.0,
+            progress.done,
+            progress.total,
+            progress.throughput(),
+            eta
+        );
+        let _ = stderr.flush();
+    }
+
+    /// Ends the progress line so later output starts on a fresh line.
+    pub fn finish(&self) {
+        if self.enabled {
+            eprintln!();
+        }
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
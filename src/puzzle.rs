@@ -0,0 +1,498 @@
+use crate::alphabet::{Alphabet, MAX_LETTERS};
+use crate::error::Error;
+use crate::packed::WILDCARD;
+use crate::scoring::{BongoRules, ScoringRules};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct Letter {
+    pub ch: char,
+    pub num: u8,
+    pub score: u32,
+}
+
+pub const POSSIBLE_LETTERS: [Letter; 14] = [
+    Letter {
+        ch: 'G',
+        num: 1,
+        score: 45,
+    },
+    Letter {
+        ch: 'B',
+        num: 3,
+        score: 50,
+    },
+    Letter {
+        ch: 'M',
+        num: 1,
+        score: 35,
+    },
+    Letter {
+        ch: 'D',
+        num: 1,
+        score: 30,
+    },
+    Letter {
+        ch: 'N',
+        num: 2,
+        score: 20,
+    },
+    Letter {
+        ch: 'U',
+        num: 1,
+        score: 15,
+    },
+    Letter {
+        ch: 'L',
+        num: 1,
+        score: 9,
+    },
+    Letter {
+        ch: 'T',
+        num: 2,
+        score: 10,
+    },
+    Letter {
+        ch: 'O',
+        num: 2,
+        score: 7,
+    },
+    Letter {
+        ch: 'R',
+        num: 2,
+        score: 7,
+    },
+    Letter {
+        ch: 'S',
+        num: 3,
+        score: 5,
+    },
+    Letter {
+        ch: 'A',
+        num: 4,
+        score: 5,
+    },
+    Letter {
+        ch: 'E',
+        num: 2,
+        score: 5,
+    },
+    Letter {
+        ch: '*',
+        num: 1,   // Wildcard
+        score: 0, // Wildcard has no score
+    },
+];
+
+pub const SCHEMA: [[u32; 5]; 5] = [
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 2, 1, 3, 1],
+    [1, 1, 1, 2, 1],
+];
+
+pub const BONUS_WORD_INDS: [(usize, usize); 4] = [(0, 2), (1, 2), (2, 2), (3, 3)];
+
+/// Most entries the `letters` line can have, as share codes count them in a
+/// byte.
+pub const MAX_LETTER_ENTRIES: usize = 255;
+/// Highest score a letter can have.
+pub const MAX_LETTER_SCORE: u32 = 1000;
+/// Highest multiplier a cell of the schema can have.
+pub const MAX_CELL_MULTIPLIER: u32 = 1000;
+/// Highest numerator or denominator of the common-word multiplier.
+pub const MAX_RATIO_TERM: u32 = 100;
+
+/// Count of each letter by its alphabet index, with the wildcard's count last.
+pub type LetterBag = [u8; MAX_LETTERS + 1];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    Floor,
+    Ceil,
+    /// Nearest integer, halves round up.
+    Nearest,
+}
+
+impl RoundingMode {
+    /// `num / den`, rounded. Works in 128 bits, since `num` is usually a score
+    /// already multiplied by a ratio's numerator, or several over a common
+    /// denominator.
+    pub fn divide(self, num: u128, den: u128) -> u32 {
+        let quotient = match self {
+            RoundingMode::Floor => num / den,
+            RoundingMode::Ceil => num.div_ceil(den),
+            RoundingMode::Nearest => (2 * num + den) / (2 * den),
+        };
+        u32::try_from(quotient).unwrap_or(u32::MAX)
+    }
+}
+
+/// Whether each word is rounded on its own, or the board total is rounded once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingScope {
+    PerWord,
+    PerBoard,
+}
+
+/// An exact ratio, e.g. 13/10 for the x1.3 common-word bonus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Multiplier {
+    pub num: u32,
+    pub den: u32,
+}
+
+impl Multiplier {
+    /// Leaves a score as it is.
+    pub const ONE: Multiplier = Multiplier { num: 1, den: 1 };
+}
+
+/// Everything that defines one day's game: the tiles, the multiplier grid, the
+/// bonus path and how word scores are multiplied and rounded.
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    /// Name of the day's puzzle, e.g. its date, if the file gives one.
+    pub id: Option<String>,
+    /// Every letter the puzzle and its words can use.
+    pub alphabet: Alphabet,
+    pub letters: Vec<Letter>,
+    pub schema: [[u32; 5]; 5],
+    pub bonus_path: [(usize, usize); 4],
+    /// Applied to every common word, the bonus word included.
+    pub common_multiplier: Multiplier,
+    pub rounding: RoundingMode,
+    pub rounding_scope: RoundingScope,
+    /// How all of the above turn into points.
+    pub rules: Arc<dyn ScoringRules>,
+}
+
+impl Default for Puzzle {
+    fn default() -> Self {
+        Puzzle {
+            id: None,
+            alphabet: Alphabet::latin(),
+            letters: POSSIBLE_LETTERS.to_vec(),
+            schema: SCHEMA,
+            bonus_path: BONUS_WORD_INDS,
+            common_multiplier: Multiplier { num: 13, den: 10 },
+            rounding: RoundingMode::Ceil,
+            rounding_scope: RoundingScope::PerWord,
+            rules: Arc::new(BongoRules),
+        }
+    }
+}
+
+impl Puzzle {
+    /// Bag index of `c`: its place in the alphabet, or `WILDCARD` for `*`.
+    pub fn letter_index(&self, c: char) -> Option<usize> {
+        if c == '*' {
+            return Some(WILDCARD);
+        }
+        self.alphabet.index_of(c)
+    }
+
+    pub fn letter_bag(&self) -> LetterBag {
+        let mut letter_bag: LetterBag = [0; MAX_LETTERS + 1];
+        for l in &self.letters {
+            if let Some(i) = self.letter_index(l.ch) {
+                letter_bag[i] = l.num as _;
+            }
+        }
+        letter_bag
+    }
+
+    pub fn letter_score(&self, c: char) -> u32 {
+        self.letters
+            .iter()
+            .find(|l| l.ch == c)
+            .map_or(0, |l| l.score)
+    }
+
+    /// Points for `letter` on the cell at `row`, `col`.
+    pub fn cell_score(&self, row: usize, col: usize, letter: char) -> u32 {
+        self.rules.letter_value(self, letter) * self.rules.cell_multiplier(self, row, col)
+    }
+
+    /// Points for the wildcard on the cell at `row`, `col`.
+    pub fn wildcard_cell_score(&self, row: usize, col: usize) -> u32 {
+        self.rules.wildcard_value(self) * self.rules.cell_multiplier(self, row, col)
+    }
+
+    /// Checks the letter scores, cell multipliers and common-word multiplier
+    /// are small enough that no board's score can overflow, and that there
+    /// are few enough letter entries for a share code.
+    pub fn check_limits(&self) -> Result<(), Error> {
+        if self.letters.len() > MAX_LETTER_ENTRIES {
+            return Err(Error::Parse(format!(
+                "{} letter entries, more than the most a puzzle can have, {MAX_LETTER_ENTRIES}",
+                self.letters.len()
+            )));
+        }
+        if let Some(l) = self.letters.iter().find(|l| l.score > MAX_LETTER_SCORE) {
+            return Err(Error::Parse(format!(
+                "{:?} scores {}, more than the most a letter can, {MAX_LETTER_SCORE}",
+                l.ch, l.score
+            )));
+        }
+        if self
+            .schema
+            .iter()
+            .flatten()
+            .any(|&m| m > MAX_CELL_MULTIPLIER)
+        {
+            return Err(Error::Parse(format!(
+                "cell multipliers can't be more than {MAX_CELL_MULTIPLIER}"
+            )));
+        }
+        let Multiplier { num, den } = self.common_multiplier;
+        if num > MAX_RATIO_TERM || den > MAX_RATIO_TERM {
+            return Err(Error::Parse(format!(
+                "the multiplier {num}/{den} can't have terms over {MAX_RATIO_TERM}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Final score of a single row word from the sum of its cells.
+    pub fn word_score(&self, raw: u32, common: bool) -> u32 {
+        let m = self.rules.word_multiplier(self, common);
+        let rounding = self.rules.rounding(self).0;
+        rounding.divide(raw as u128 * m.num as u128, m.den as u128)
+    }
+
+    /// Final score of a board from the raw (cell sum) score of each row word and
+    /// of the bonus word, if it was spelled, each with whether it's common.
+    pub fn board_score(
+        &self,
+        raw_word_scores: impl IntoIterator<Item = (u32, bool)>,
+        raw_bonus_score: Option<(u32, bool)>,
+    ) -> u32 {
+        let words = raw_word_scores
+            .into_iter()
+            .map(|(raw, common)| (raw, self.rules.word_multiplier(self, common)));
+        let bonus = raw_bonus_score
+            .and_then(|(raw, common)| Some((raw, self.rules.bonus_multiplier(self, common)?)));
+        let (rounding, scope) = self.rules.rounding(self);
+        let terms = words.chain(bonus);
+        match scope {
+            RoundingScope::PerWord => terms
+                .map(|(raw, m)| rounding.divide(raw as u128 * m.num as u128, m.den as u128))
+                .fold(0, u32::saturating_add),
+            RoundingScope::PerBoard => {
+                // Add everything up as one exact fraction, then round once
+                let (num, den) = terms.fold((0, 1), |(num, den), (raw, m)| {
+                    let term = raw as u128 * m.num as u128;
+                    (num * m.den as u128 + term * den, den * m.den as u128)
+                });
+                rounding.divide(num, den)
+            }
+        }
+    }
+}
+
+/// Reads a puzzle from `key = value` lines, e.g.
+///
+/// ```text
+/// letters = B:3:50 G:1:45 A:4:5 *:1:0
+/// schema = 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1 / 1 2 1 3 1 / 1 1 1 2 1
+/// bonus = 0,2 1,2 2,2 3,3
+/// multiplier = 13/10
+/// rounding = ceil per-word
+/// ```
+///
+/// Letters are `char:count:score`. `multiplier` and `rounding` are optional and
+/// default to the game's x1.3 rounded up per word. `alphabet` is optional too,
+/// e.g. `alphabet = ABCDEFGHIJKLMNÑOPQRSTUVWXYZ`; without it, the alphabet is A-Z
+/// plus any other letters the tiles use. An optional `id` names the puzzle, e.g.
+/// `id = 2024-06-01`. Blank lines and `#` comments are ignored.
+impl FromStr for Puzzle {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut puzzle = Puzzle::default();
+        let (mut letters, mut schema, mut bonus) = (false, false, false);
+        let mut alphabet = None;
+        for line in s.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| Error::Parse(format!("expected `key = value`, got `{line}`")))?;
+            let value = value.trim();
+            match key.trim() {
+                "id" => puzzle.id = Some(value.to_string()),
+                "alphabet" => alphabet = Some(value.parse::<Alphabet>()?),
+                "letters" => {
+                    puzzle.letters = value
+                        .split_whitespace()
+                        .map(parse_letter)
+                        .collect::<Result<_, _>>()?;
+                    letters = true;
+                }
+                "schema" => {
+                    puzzle.schema = parse_grid(value)?;
+                    schema = true;
+                }
+                "bonus" => {
+                    puzzle.bonus_path = parse_path(value)?;
+                    bonus = true;
+                }
+                "multiplier" => puzzle.common_multiplier = value.parse()?,
+                "rounding" => {
+                    for word in value.split_whitespace() {
+                        match word {
+                            "floor" => puzzle.rounding = RoundingMode::Floor,
+                            "ceil" => puzzle.rounding = RoundingMode::Ceil,
+                            "nearest" => puzzle.rounding = RoundingMode::Nearest,
+                            "per-word" => puzzle.rounding_scope = RoundingScope::PerWord,
+                            "per-board" => puzzle.rounding_scope = RoundingScope::PerBoard,
+                            _ => return Err(Error::Parse(format!("unknown rounding `{word}`"))),
+                        }
+                    }
+                }
+                other => return Err(Error::Parse(format!("unknown key `{other}`"))),
+            }
+        }
+        for (present, key) in [(letters, "letters"), (schema, "schema"), (bonus, "bonus")] {
+            if !present {
+                return Err(Error::Parse(format!("missing `{key}`")));
+            }
+        }
+        let tiles = puzzle.letters.iter().map(|l| l.ch).filter(|&c| c != '*');
+        puzzle.alphabet = match alphabet {
+            // Letters beyond A-Z are added in the order the tiles list them
+            None => Alphabet::latin().with(tiles)?,
+            Some(alphabet) => {
+                if let Some(c) = tiles.clone().find(|&c| !alphabet.contains(c)) {
+                    return Err(Error::Parse(format!("letter {c:?} isn't in the alphabet")));
+                }
+                alphabet
+            }
+        };
+        puzzle.check_limits()?;
+        Ok(puzzle)
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(id) = &self.id {
+            writeln!(f, "id = {id}")?;
+        }
+        if self.alphabet != Alphabet::latin() {
+            writeln!(f, "alphabet = {}", self.alphabet)?;
+        }
+        let letters = self
+            .letters
+            .iter()
+            .map(|l| format!("{}:{}:{}", l.ch, l.num, l.score))
+            .collect::<Vec<_>>();
+        writeln!(f, "letters = {}", letters.join(" "))?;
+        let rows = self
+            .schema
+            .iter()
+            .map(|row| row.map(|m| m.to_string()).join(" "))
+            .collect::<Vec<_>>();
+        writeln!(f, "schema = {}", rows.join(" / "))?;
+        let path = self
+            .bonus_path
+            .iter()
+            .map(|(r, c)| format!("{r},{c}"))
+            .collect::<Vec<_>>();
+        writeln!(f, "bonus = {}", path.join(" "))?;
+        writeln!(f, "multiplier = {}", self.common_multiplier)?;
+        let rounding = match self.rounding {
+            RoundingMode::Floor => "floor",
+            RoundingMode::Ceil => "ceil",
+            RoundingMode::Nearest => "nearest",
+        };
+        let scope = match self.rounding_scope {
+            RoundingScope::PerWord => "per-word",
+            RoundingScope::PerBoard => "per-board",
+        };
+        writeln!(f, "rounding = {rounding} {scope}")
+    }
+}
+
+impl FromStr for Multiplier {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || Error::Parse(format!("bad multiplier `{s}`, expected e.g. `13/10`"));
+        let (num, den) = s.split_once('/').unwrap_or((s, "1"));
+        let num = num.trim().parse().map_err(|_| bad())?;
+        let den = den.trim().parse().map_err(|_| bad())?;
+        if den == 0 {
+            return Err(bad());
+        }
+        Ok(Multiplier { num, den })
+    }
+}
+
+impl fmt::Display for Multiplier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
+}
+
+fn parse_letter(s: &str) -> Result<Letter, Error> {
+    let bad = || Error::Parse(format!("bad letter `{s}`, expected `char:count:score`"));
+    let mut parts = s.split(':');
+    let (Some(ch), Some(num), Some(score), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(bad());
+    };
+    let mut chars = ch.chars();
+    let (Some(ch), None) = (chars.next(), chars.next()) else {
+        return Err(bad());
+    };
+    if !((ch.is_alphabetic() && ch.is_uppercase()) || ch == '*') {
+        return Err(bad());
+    }
+    Ok(Letter {
+        ch,
+        num: num.parse().map_err(|_| bad())?,
+        score: score.parse().map_err(|_| bad())?,
+    })
+}
+
+fn parse_grid(s: &str) -> Result<[[u32; 5]; 5], Error> {
+    let bad = || {
+        Error::Parse(format!(
+            "bad schema `{s}`, expected 5 rows of 5 multipliers split by `/`"
+        ))
+    };
+    let rows = s
+        .split('/')
+        .map(|row| {
+            let row = row
+                .split_whitespace()
+                .map(|m| m.parse().map_err(|_| bad()))
+                .collect::<Result<Vec<u32>, _>>()?;
+            row.try_into().map_err(|_| bad())
+        })
+        .collect::<Result<Vec<[u32; 5]>, _>>()?;
+    rows.try_into().map_err(|_| bad())
+}
+
+fn parse_path(s: &str) -> Result<[(usize, usize); 4], Error> {
+    let bad = || Error::Parse(format!("bad bonus path `{s}`, expected 4 `row,col` cells"));
+    let cells = s
+        .split_whitespace()
+        .map(|cell| {
+            let (r, c) = cell.split_once(',').ok_or_else(bad)?;
+            let (r, c) = (r.parse().map_err(|_| bad())?, c.parse().map_err(|_| bad())?);
+            if r >= 5 || c >= 5 {
+                return Err(bad());
+            }
+            Ok((r, c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    cells.try_into().map_err(|_| bad())
+}
@@ -0,0 +1,169 @@
+//! Boards drawn as SVG, laid out like the game: the 5x5 grid with its
+//! multiplier cells, the bonus path and the wildcard marked, each letter's
+//! score in its corner and the row scores down the side.
+
+use crate::packed::PackedWord;
+use crate::puzzle::Puzzle;
+use crate::solver::{Bonus, score_breakdown};
+use std::fmt::Write;
+
+const CELL: u32 = 64;
+const GAP: u32 = 6;
+const MARGIN: u32 = 16;
+const HEADER: u32 = 36;
+const FOOTER: u32 = 40;
+/// Width of the row score column.
+const SCORES: u32 = 64;
+
+const BACKGROUND: &str = "#f4f1ea";
+const TILE: &str = "#ffffff";
+const BONUS: &str = "#f6d365";
+const DOUBLE: &str = "#b9d7f0";
+const TRIPLE: &str = "#c9b3e6";
+const INK: &str = "#2b2b2b";
+const FAINT: &str = "#7a7a7a";
+const WILDCARD: &str = "#d9534f";
+
+/// Escapes `text` for use in XML or HTML.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Top left corner of the cell at `row`, `col`.
+fn cell_origin(row: usize, col: usize) -> (u32, u32) {
+    (
+        MARGIN + col as u32 * (CELL + GAP),
+        MARGIN + HEADER + row as u32 * (CELL + GAP),
+    )
+}
+
+/// `board` as a standalone SVG document, scored with `bonus` for its bonus
+/// path. Rows missing from the board are drawn as empty tiles, so an empty
+/// board shows the puzzle's layout.
+pub fn board_svg(board: &[&PackedWord], bonus: Bonus, puzzle: &Puzzle) -> String {
+    let grid = 5 * CELL + 4 * GAP;
+    let width = 2 * MARGIN + grid + SCORES;
+    let height = 2 * MARGIN + HEADER + grid + FOOTER;
+    let complete = board.len() == 5;
+    let breakdown = complete.then(|| score_breakdown(board, bonus, puzzle));
+    let wildcard = breakdown.as_ref().and_then(|b| b.wildcard);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="Helvetica, Arial, sans-serif">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="{width}" height="{height}" rx="12" fill="{BACKGROUND}"/>"#
+    );
+    let title = puzzle.id.as_deref().unwrap_or("Bongo");
+    let _ = writeln!(
+        svg,
+        r#"<text x="{MARGIN}" y="{}" font-size="22" font-weight="bold" fill="{INK}">{}</text>"#,
+        MARGIN + 24,
+        escape(title)
+    );
+
+    for row in 0..5 {
+        for col in 0..5 {
+            let (x, y) = cell_origin(row, col);
+            let multiplier = puzzle.rules.cell_multiplier(puzzle, row, col);
+            let fill = if puzzle.bonus_path.contains(&(row, col)) {
+                BONUS
+            } else {
+                match multiplier {
+                    0 | 1 => TILE,
+                    2 => DOUBLE,
+                    _ => TRIPLE,
+                }
+            };
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{x}" y="{y}" width="{CELL}" height="{CELL}" rx="6" fill="{fill}" stroke="{FAINT}" stroke-width="1"/>"#
+            );
+            if multiplier > 1 {
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="11" fill="{FAINT}">×{multiplier}</text>"#,
+                    x + 5,
+                    y + 14
+                );
+            }
+            let Some(letter) = board.get(row).and_then(|w| w.word.chars().nth(col)) else {
+                continue;
+            };
+            let is_wildcard = wildcard == Some((row, col));
+            let value = if is_wildcard {
+                puzzle.rules.wildcard_value(puzzle)
+            } else {
+                puzzle.rules.letter_value(puzzle, letter)
+            };
+            if is_wildcard {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" rx="5" fill="none" stroke="{WILDCARD}" stroke-width="3" stroke-dasharray="6 4"/>"#,
+                    x + 2,
+                    y + 2,
+                    CELL - 4,
+                    CELL - 4
+                );
+            }
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-size="32" font-weight="bold" text-anchor="middle" fill="{}">{letter}</text>"#,
+                x + CELL / 2,
+                y + CELL / 2 + 11,
+                if is_wildcard { WILDCARD } else { INK }
+            );
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-size="12" text-anchor="end" fill="{FAINT}">{value}</text>"#,
+                x + CELL - 5,
+                y + CELL - 6
+            );
+        }
+        if let Some(score) = breakdown.as_ref().map(|b| b.rows[row]) {
+            let (x, y) = cell_origin(row, 5);
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-size="18" text-anchor="end" fill="{INK}">{score}</text>"#,
+                x - GAP + SCORES,
+                y + CELL / 2 + 6
+            );
+        }
+    }
+
+    if let Some(breakdown) = &breakdown {
+        let bonus_word = puzzle
+            .bonus_path
+            .iter()
+            .filter_map(|&(row, col)| board[row].word.chars().nth(col))
+            .collect::<String>();
+        let bonus = breakdown.bonus.unwrap_or(0);
+        let y = height - MARGIN - 12;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{MARGIN}" y="{y}" font-size="16" fill="{INK}">Bonus {bonus_word} +{bonus}</text>"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{y}" font-size="20" font-weight="bold" text-anchor="end" fill="{INK}">Total {}</text>"#,
+            width - MARGIN,
+            breakdown.total
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,211 @@
+//! Machine-readable solve results: a JSON document, JSON Lines with one board
+//! per line, or CSV with one board per row.
+
+use crate::packed::PackedWord;
+use crate::puzzle::Puzzle;
+use crate::solver::{Board, Bonus, bonus_of, score_breakdown};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// How a solve went, apart from the boards it found.
+#[derive(Clone, Debug, Serialize)]
+pub struct SolveStats {
+    pub strategy: String,
+    /// Dictionary words that fit the bag.
+    pub dictionary_words: usize,
+    pub bonus_candidates: usize,
+    pub row_candidates: usize,
+    /// Boards the strategy came back with, before taking the top ones.
+    pub boards_found: usize,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RowReport {
+    pub word: String,
+    /// Whether the word is common, and so got the puzzle's multiplier.
+    pub common: bool,
+    pub score: u32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct WildcardReport {
+    pub row: usize,
+    pub col: usize,
+    /// The letter the wildcard stands in for.
+    pub letter: char,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BoardReport {
+    /// 1 for the best board.
+    pub rank: usize,
+    pub rows: Vec<RowReport>,
+    pub wildcard: Option<WildcardReport>,
+    pub bonus_word: String,
+    pub bonus_common: bool,
+    pub bonus_score: Option<u32>,
+    pub total: u32,
+}
+
+impl BoardReport {
+    pub fn new(rank: usize, board: &[&PackedWord], bonus: Bonus, puzzle: &Puzzle) -> Self {
+        let breakdown = score_breakdown(board, bonus, puzzle);
+        let letter_at = |(r, c): (usize, usize)| board[r].word.chars().nth(c).unwrap_or('?');
+        BoardReport {
+            rank,
+            rows: board
+                .iter()
+                .zip(&breakdown.rows)
+                .map(|(word, &score)| RowReport {
+                    word: word.word.clone(),
+                    common: word.common,
+                    score,
+                })
+                .collect(),
+            wildcard: breakdown.wildcard.map(|(row, col)| WildcardReport {
+                row,
+                col,
+                letter: letter_at((row, col)),
+            }),
+            bonus_word: puzzle
+                .bonus_path
+                .iter()
+                .map(|&cell| letter_at(cell))
+                .collect(),
+            bonus_common: bonus == Bonus::Spelled { common: true },
+            bonus_score: breakdown.bonus,
+            total: breakdown.total,
+        }
+    }
+
+    /// What the bonus path spells, as far as the board's score went.
+    pub fn bonus(&self) -> Bonus {
+        match self.bonus_score {
+            Some(_) => Bonus::Spelled {
+                common: self.bonus_common,
+            },
+            None => Bonus::Missed,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SolveReport {
+    pub puzzle_id: String,
+    pub stats: SolveStats,
+    /// Best first.
+    pub boards: Vec<BoardReport>,
+}
+
+/// One line of JSON Lines output: a board with what it was solved from.
+#[derive(Serialize)]
+struct BoardLine<'a> {
+    puzzle_id: &'a str,
+    #[serde(flatten)]
+    board: &'a BoardReport,
+    stats: &'a SolveStats,
+}
+
+impl SolveReport {
+    /// A report on `boards`, which should be best first, with their bonus
+    /// paths looked up in the `bonus_words` they were solved with.
+    pub fn new(
+        puzzle_id: impl Into<String>,
+        stats: SolveStats,
+        boards: &[(Board, u32)],
+        bonus_words: &[&PackedWord],
+        puzzle: &Puzzle,
+    ) -> Self {
+        SolveReport {
+            puzzle_id: puzzle_id.into(),
+            stats,
+            boards: boards
+                .iter()
+                .enumerate()
+                .map(|(i, (board, _))| {
+                    let bonus = bonus_of(board, bonus_words, puzzle);
+                    BoardReport::new(i + 1, board, bonus, puzzle)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn write_json(&self, mut out: impl Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut out, self)?;
+        writeln!(out)
+    }
+
+    pub fn write_json_lines(&self, mut out: impl Write) -> io::Result<()> {
+        for board in &self.boards {
+            let line = BoardLine {
+                puzzle_id: &self.puzzle_id,
+                board,
+                stats: &self.stats,
+            };
+            serde_json::to_writer(&mut out, &line)?;
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// One row per board, with the rows, their scores and the stats spread over
+    /// fixed columns.
+    pub fn write_csv(&self, out: impl Write) -> io::Result<()> {
+        let mut csv = csv::Writer::from_writer(out);
+        let rows = self.boards.iter().map(|b| b.rows.len()).max().unwrap_or(5);
+        let mut header = vec![
+            "puzzle_id".to_string(),
+            "rank".to_string(),
+            "total".to_string(),
+        ];
+        header.extend((1..=rows).map(|i| format!("row{i}")));
+        header.extend((1..=rows).map(|i| format!("row{i}_score")));
+        header.extend(
+            [
+                "bonus_word",
+                "bonus_score",
+                "wildcard_row",
+                "wildcard_col",
+                "wildcard_letter",
+                "strategy",
+                "dictionary_words",
+                "bonus_candidates",
+                "row_candidates",
+                "boards_found",
+                "elapsed_ms",
+            ]
+            .map(String::from),
+        );
+        csv.write_record(&header)?;
+
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        let stats = &self.stats;
+        for board in &self.boards {
+            let mut record = vec![
+                self.puzzle_id.clone(),
+                board.rank.to_string(),
+                board.total.to_string(),
+            ];
+            let row = |i: usize| board.rows.get(i);
+            record.extend((0..rows).map(|i| optional(row(i).map(|r| r.word.clone()))));
+            record.extend((0..rows).map(|i| optional(row(i).map(|r| r.score.to_string()))));
+            let wildcard = board.wildcard.as_ref();
+            record.extend([
+                board.bonus_word.clone(),
+                optional(board.bonus_score.map(|s| s.to_string())),
+                optional(wildcard.map(|w| w.row.to_string())),
+                optional(wildcard.map(|w| w.col.to_string())),
+                optional(wildcard.map(|w| w.letter.to_string())),
+                stats.strategy.clone(),
+                stats.dictionary_words.to_string(),
+                stats.bonus_candidates.to_string(),
+                stats.row_candidates.to_string(),
+                stats.boards_found.to_string(),
+                stats.elapsed_ms.to_string(),
+            ]);
+            csv.write_record(&record)?;
+        }
+        csv.flush()
+    }
+}
@@ -0,0 +1,68 @@
+//! How tiles, cells and words turn into points.
+//!
+//! The search only ever sees scores through `Puzzle`, which asks its
+//! `ScoringRules` for them, so house variants or rule changes only need a new
+//! `ScoringRules` implementation.
+
+use crate::puzzle::{Multiplier, Puzzle, RoundingMode, RoundingScope};
+use std::fmt;
+
+/// Every method gets the puzzle being scored, since the day's letter values,
+/// schema and multiplier are inputs to the rules rather than part of them.
+pub trait ScoringRules: fmt::Debug + Send + Sync {
+    /// Points for one tile of `letter`.
+    fn letter_value(&self, puzzle: &Puzzle, letter: char) -> u32;
+
+    /// Multiplier of the cell at `row`, `col`.
+    fn cell_multiplier(&self, puzzle: &Puzzle, row: usize, col: usize) -> u32;
+
+    /// Points for the wildcard tile, whichever letter it stands in for.
+    fn wildcard_value(&self, puzzle: &Puzzle) -> u32;
+
+    /// Applied to the cell sum of a row word, which the game counts as common
+    /// or not.
+    fn word_multiplier(&self, puzzle: &Puzzle, common: bool) -> Multiplier;
+
+    /// Applied to the cell sum of the bonus path, which spells a common word or
+    /// not, or `None` if the bonus word doesn't score.
+    fn bonus_multiplier(&self, puzzle: &Puzzle, common: bool) -> Option<Multiplier>;
+
+    fn rounding(&self, puzzle: &Puzzle) -> (RoundingMode, RoundingScope);
+}
+
+/// The game's own rules: letter values and cell multipliers straight from the
+/// puzzle, a wildcard worth whatever the puzzle gives `*` (nothing, so far),
+/// and the common-word multiplier on every common word, the bonus word
+/// included.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BongoRules;
+
+impl ScoringRules for BongoRules {
+    fn letter_value(&self, puzzle: &Puzzle, letter: char) -> u32 {
+        puzzle.letter_score(letter)
+    }
+
+    fn cell_multiplier(&self, puzzle: &Puzzle, row: usize, col: usize) -> u32 {
+        puzzle.schema[row][col]
+    }
+
+    fn wildcard_value(&self, puzzle: &Puzzle) -> u32 {
+        puzzle.letter_score('*')
+    }
+
+    fn word_multiplier(&self, puzzle: &Puzzle, common: bool) -> Multiplier {
+        if common {
+            puzzle.common_multiplier
+        } else {
+            Multiplier::ONE
+        }
+    }
+
+    fn bonus_multiplier(&self, puzzle: &Puzzle, common: bool) -> Option<Multiplier> {
+        Some(self.word_multiplier(puzzle, common))
+    }
+
+    fn rounding(&self, puzzle: &Puzzle) -> (RoundingMode, RoundingScope) {
+        (puzzle.rounding, puzzle.rounding_scope)
+    }
+}
@@ -0,0 +1,325 @@
+//! Boards people share, read back so their score can be checked.
+//!
+//! A shared board is any text with five consecutive lines of five letters
+//! each. Spaces and `|`, `+` and `,` between letters are ignored, so plain
+//! rows, spaced out rows and ASCII tables all work, and so do lowercase
+//! letters. A `*` right after a letter marks it as the wildcard. When more
+//! than five such lines run together, a `BONGO` title on top is skipped, and
+//! otherwise the mark has to tell which five are the grid; the text is
+//! rejected rather than guessed at. Other lines, like the game's header and
+//! emoji rows, are skipped, except that a number after "score" or before
+//! "points" or "pts" is taken as the claimed score.
+//!
+//! A checked board can be shared back without giving the words away, as a
+//! grid of squares like the game's own share text.
+//!
+//! ```text
+//! Bongo 2024-05-01 Score: 1003
+//! B A T O N
+//! B R O A D
+//! L U M E N
+//! S T A B S
+//! F*R A G S
+//! ```
+
+use crate::dictionary::Dictionary;
+use crate::error::{Error, Result};
+use crate::packed::{PackedWord, WILDCARD};
+use crate::puzzle::Puzzle;
+use crate::solver::{Board, Bonus, score_breakdown, score_breakdown_at};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Characters that can sit between the letters of a row.
+const SEPARATORS: [char; 3] = ['|', '+', ','];
+/// The game's name, which share text can have as a title right above the grid.
+const TITLE: &str = "BONGO";
+
+/// A board as someone shared it, before it's checked against a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SharedBoard {
+    /// The five row words, uppercase.
+    pub rows: Vec<String>,
+    /// The cell marked as the wildcard, if one was.
+    pub wildcard: Option<(usize, usize)>,
+    /// The score the text says the board got.
+    pub claimed_score: Option<u32>,
+}
+
+/// The letters of a grid row and the column of its wildcard mark, if `line`
+/// is one.
+fn parse_row(line: &str) -> Option<(String, Option<usize>)> {
+    let mut word = String::new();
+    let mut wildcard = None;
+    for c in line.chars() {
+        if c.is_whitespace() || SEPARATORS.contains(&c) {
+            continue;
+        }
+        if c == '*' {
+            let col = word.chars().count().checked_sub(1)?;
+            if wildcard.replace(col).is_some() {
+                return None;
+            }
+        } else if c.is_alphabetic() {
+            word.extend(c.to_uppercase());
+        } else {
+            return None;
+        }
+    }
+    (word.chars().count() == 5).then_some((word, wildcard))
+}
+
+/// The number after "score", or before "points" or "pts", in `line`.
+fn parse_score(line: &str) -> Option<u32> {
+    let tokens = line
+        .split(|c: char| !(c.is_alphanumeric() || c == ','))
+        .map(|t| t.trim_matches(','))
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>();
+    let number = |token: &&str| token.replace(',', "").parse().ok();
+    tokens
+        .iter()
+        .enumerate()
+        .find_map(|(i, token)| match token.to_lowercase().as_str() {
+            "score" => tokens.get(i + 1).and_then(number),
+            "points" | "pts" => i.checked_sub(1).and_then(|i| number(&tokens[i])),
+            _ => None,
+        })
+}
+
+impl FromStr for SharedBoard {
+    type Err = Error;
+
+    /// Takes the first run of at least five grid rows. In a longer run a
+    /// `BONGO` title on top is skipped, and otherwise the wildcard mark has to
+    /// show which five rows are the board, since a word right above or below
+    /// the grid looks just like a row.
+    fn from_str(s: &str) -> Result<Self> {
+        let lines = s
+            .lines()
+            .map(|line| (line, parse_row(line)))
+            .collect::<Vec<_>>();
+        let mut run = None;
+        let mut start = 0;
+        for (i, (_, row)) in lines.iter().enumerate() {
+            if row.is_none() {
+                start = i + 1;
+                continue;
+            }
+            let ends_run = lines.get(i + 1).is_none_or(|(_, next)| next.is_none());
+            if ends_run && i + 1 - start >= 5 {
+                run = Some(start..i + 1);
+                break;
+            }
+        }
+        let mut run = run.ok_or_else(|| {
+            Error::Parse("no grid of five rows of five letters found".to_string())
+        })?;
+        if run.len() > 5
+            && lines[run.start]
+                .1
+                .as_ref()
+                .is_some_and(|(w, col)| w == TITLE && col.is_none())
+        {
+            run.start += 1;
+        }
+
+        let marked = run
+            .clone()
+            .filter_map(|i| {
+                let (_, col) = lines[i].1.as_ref()?;
+                col.map(|col| (i, col))
+            })
+            .collect::<Vec<_>>();
+        if marked.len() > 1 {
+            return Err(Error::Parse(
+                "more than one cell is marked as the wildcard".to_string(),
+            ));
+        }
+        let starts = (run.start..=run.end - 5)
+            .filter(|&start| marked.iter().all(|&(i, _)| (start..start + 5).contains(&i)))
+            .collect::<Vec<_>>();
+        let [start] = starts[..] else {
+            return Err(Error::Parse(format!(
+                "{} rows of five letters in a row; put a blank line between the grid and \
+                 the other rows, or mark the wildcard with `*`",
+                run.len()
+            )));
+        };
+        let grid = start..start + 5;
+        let rows = lines[grid.clone()]
+            .iter()
+            .map(|(_, row)| row.clone().expect("grid lines are rows").0)
+            .collect();
+        let wildcard = marked.first().map(|&(i, col)| (i - start, col));
+        let claimed_score = lines
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !grid.contains(i))
+            .find_map(|(_, (line, _))| parse_score(line));
+        Ok(SharedBoard {
+            rows,
+            wildcard,
+            claimed_score,
+        })
+    }
+}
+
+/// A shared board that fits its puzzle, with its score.
+#[derive(Clone, Debug)]
+pub struct CheckedBoard {
+    pub words: Vec<PackedWord>,
+    /// The letters along the bonus path.
+    pub bonus_word: String,
+    /// Whether the bonus path spells a word.
+    pub bonus_word_used: bool,
+    /// Whether that word is common.
+    pub bonus_word_common: bool,
+    /// Whether each row is a common word.
+    pub common_rows: Vec<bool>,
+    /// The cell the wildcard is counted in: the one it was marked in, or if
+    /// it wasn't marked, whichever of its letter's cells scores best, as the
+    /// solver does.
+    pub wildcard: Option<(usize, usize)>,
+    pub score: u32,
+}
+
+impl SharedBoard {
+    /// Checks the board uses only the puzzle's tiles, with at most the
+    /// wildcard standing in for a missing one, and that every row is a word in
+    /// `dictionary`, then scores it.
+    pub fn check(&self, puzzle: &Puzzle, dictionary: &dyn Dictionary) -> Result<CheckedBoard> {
+        let mut bag = puzzle.letter_bag();
+        let mut wildcard_letter = None;
+        for (r, word) in self.rows.iter().enumerate() {
+            for (col, c) in word.chars().enumerate() {
+                let i = puzzle.alphabet.index_of(c).ok_or_else(|| {
+                    Error::Invalid(format!("{c:?} in row {} isn't in the puzzle", r + 1))
+                })?;
+                if self.wildcard != Some((r, col)) && bag[i] > 0 {
+                    bag[i] -= 1;
+                } else if bag[WILDCARD] > 0 && wildcard_letter.is_none() {
+                    bag[WILDCARD] -= 1;
+                    wildcard_letter = Some(c);
+                } else {
+                    return Err(Error::Invalid(format!(
+                        "the board uses more {c} tiles than the puzzle has"
+                    )));
+                }
+            }
+        }
+        for (r, word) in self.rows.iter().enumerate() {
+            if !dictionary.contains(word) {
+                return Err(Error::Invalid(format!(
+                    "row {} `{word}` isn't in the dictionary",
+                    r + 1
+                )));
+            }
+        }
+
+        // A marked wildcard stays where it was put; otherwise it's tried in
+        // every cell with its letter, as `score_board` does
+        let marked = self.wildcard.filter(|_| wildcard_letter.is_some());
+        let wildcard_row = wildcard_letter.and_then(|c| match marked {
+            Some((row, _)) => Some(row),
+            None => self.rows.iter().position(|w| w.contains(c)),
+        });
+        let words = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(r, word)| {
+                let wildcard = wildcard_letter.filter(|_| wildcard_row == Some(r));
+                let mut word = PackedWord::new(word, wildcard, puzzle);
+                word.common = dictionary.is_common(&word.word);
+                word
+            })
+            .collect::<Vec<_>>();
+        let bonus_word = puzzle
+            .bonus_path
+            .iter()
+            .filter_map(|&(row, col)| self.rows[row].chars().nth(col))
+            .collect::<String>();
+        let bonus_word_used = dictionary.contains(&bonus_word);
+        let bonus_word_common = bonus_word_used && dictionary.is_common(&bonus_word);
+        let common_rows = words.iter().map(|w| w.common).collect();
+        let mut checked = CheckedBoard {
+            words,
+            bonus_word,
+            bonus_word_used,
+            bonus_word_common,
+            common_rows,
+            wildcard: None,
+            score: 0,
+        };
+        let board = checked.board();
+        let breakdown = match marked {
+            Some(cell) => score_breakdown_at(&board, checked.bonus(), Some(cell), puzzle),
+            None => score_breakdown(&board, checked.bonus(), puzzle),
+        };
+        checked.wildcard = breakdown.wildcard;
+        checked.score = breakdown.total;
+        Ok(checked)
+    }
+}
+
+impl CheckedBoard {
+    pub fn board(&self) -> Board<'_> {
+        self.words.iter().collect()
+    }
+
+    /// What the bonus path spells, for scoring.
+    pub fn bonus(&self) -> Bonus {
+        if self.bonus_word_used {
+            Bonus::Spelled {
+                common: self.bonus_word_common,
+            }
+        } else {
+            Bonus::Missed
+        }
+    }
+
+    /// A spoiler-free summary: a square per cell, blue or purple where a
+    /// multiplier is, yellow along the bonus path if it spelled a word, and
+    /// green or white for the rest of a common or uncommon row; each row's
+    /// score; and how close the board came to `optimum`.
+    ///
+    /// ```text
+    /// Bongo gbm · 1003/1003 (100%)
+    /// 🟩🟩🟨🟩🟩 120
+    /// ```
+    pub fn summary(&self, puzzle: &Puzzle, optimum: u32) -> String {
+        let breakdown = score_breakdown_at(&self.board(), self.bonus(), self.wildcard, puzzle);
+        let mut summary = format!("Bongo {}", puzzle.id.as_deref().unwrap_or("puzzle"));
+        let _ = write!(summary, " · {}", self.score);
+        if optimum > 0 {
+            let percent = 100 * self.score as u64 / optimum as u64;
+            let _ = write!(summary, "/{optimum} ({percent}%)");
+        }
+        summary.push('\n');
+        for (row, score) in breakdown.rows.iter().enumerate() {
+            for col in 0..5 {
+                let square = if puzzle.bonus_path.contains(&(row, col)) {
+                    if self.bonus_word_used { '🟨' } else { '⬛' }
+                } else {
+                    match puzzle.rules.cell_multiplier(puzzle, row, col) {
+                        0 | 1 if self.common_rows[row] => '🟩',
+                        0 | 1 => '⬜',
+                        2 => '🟦',
+                        _ => '🟪',
+                    }
+                };
+                summary.push(square);
+            }
+            let _ = writeln!(summary, " {score}");
+        }
+        let common = self.common_rows.iter().filter(|&&c| c).count();
+        let _ = writeln!(
+            summary,
+            "Bonus word {} · {common}/{} common words",
+            if self.bonus_word_used { '✅' } else { '❌' },
+            self.common_rows.len()
+        );
+        summary
+    }
+}
@@ -0,0 +1,579 @@
+use crate::dictionary::Dictionary;
+use crate::error::Error;
+use crate::packed::{MAX_WORD_LEN, PackedWord, WILDCARD};
+use crate::progress::{Progress, ProgressCounter};
+use crate::puzzle::{LetterBag, Puzzle};
+use rayon::prelude::*;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub type Board<'a> = Vec<&'a PackedWord>;
+
+pub fn remove_word_from_bag(word: &PackedWord, letter_bag: &LetterBag) -> Option<LetterBag> {
+    let mut new_letter_bag = *letter_bag;
+    let mut missing = 0;
+    for (have, &need) in new_letter_bag.iter_mut().zip(&word.counts) {
+        missing += need.saturating_sub(*have);
+        *have = have.saturating_sub(need);
+    }
+    if missing == 0 {
+        Some(new_letter_bag)
+    } else if word.wildcard.is_some() && new_letter_bag[WILDCARD] >= missing {
+        new_letter_bag[WILDCARD] -= missing;
+        Some(new_letter_bag)
+    } else {
+        None
+    }
+}
+
+/// Puts a word removed from `full_bag` back into `letter_bag`. Letters beyond
+/// what `full_bag` holds must have come from the wildcard, so they go back to it.
+pub fn add_word_to_bag(
+    word: &PackedWord,
+    letter_bag: &LetterBag,
+    full_bag: &LetterBag,
+) -> LetterBag {
+    let mut new_letter_bag = *letter_bag;
+    for (i, &count) in word.counts.iter().enumerate() {
+        let returned = count.min(full_bag[i].saturating_sub(new_letter_bag[i]));
+        new_letter_bag[i] += returned;
+        new_letter_bag[WILDCARD] += count - returned;
+    }
+    new_letter_bag
+}
+
+/// Upper bound on what `word` can score in `row` of any board. Even when the
+/// word needs the wildcard, the board may put it on the same letter in another
+/// row, so the full word score is the bound, unless the wildcard is worth more
+/// than one of the word's own cells.
+pub fn prescore_word_in_row(row: usize, word: &PackedWord, puzzle: &Puzzle) -> u32 {
+    let wildcard_gain = (0..word.len)
+        .map(|col| {
+            puzzle
+                .wildcard_cell_score(row, col)
+                .saturating_sub(word.cell_scores[row][col])
+        })
+        .max()
+        .unwrap_or(0);
+    puzzle.word_score(word.row_totals[row] + wildcard_gain, word.common)
+}
+
+pub fn score_word(
+    row: usize,
+    word: &PackedWord,
+    wildcard_index: Option<(usize, usize)>,
+    puzzle: &Puzzle,
+) -> u32 {
+    puzzle.word_score(
+        raw_word_score(row, word, wildcard_index, puzzle),
+        word.common,
+    )
+}
+
+/// Sum of the word's cells in `row`, with the wildcard's value in its cell.
+fn raw_word_score(
+    row: usize,
+    word: &PackedWord,
+    wildcard_index: Option<(usize, usize)>,
+    puzzle: &Puzzle,
+) -> u32 {
+    let mut word_score = word.row_totals[row];
+    if let Some((wild_row, wild_col)) = wildcard_index
+        && wild_row == row
+    {
+        word_score -= word.cell_scores[row][wild_col];
+        word_score += puzzle.wildcard_cell_score(row, wild_col);
+    }
+    word_score
+}
+
+fn raw_bonus_word_score(
+    board: &[&PackedWord],
+    wildcard_index: Option<(usize, usize)>,
+    puzzle: &Puzzle,
+) -> u32 {
+    let mut word_score = 0;
+    for &(r, c) in puzzle.bonus_path.iter() {
+        word_score += if wildcard_index == Some((r, c)) {
+            puzzle.wildcard_cell_score(r, c)
+        } else {
+            board[r].cell_scores[r][c]
+        };
+    }
+    word_score
+}
+
+/// What a board's bonus path spells, as far as its score goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bonus {
+    /// Not a word, so the path scores nothing.
+    Missed,
+    /// A word, which only gets the puzzle's multiplier if it's common.
+    Spelled { common: bool },
+}
+
+impl Bonus {
+    /// The bonus path's raw score with whether it's common, if it scores.
+    fn raw_score(
+        self,
+        board: &[&PackedWord],
+        wildcard_index: Option<(usize, usize)>,
+        puzzle: &Puzzle,
+    ) -> Option<(u32, bool)> {
+        match self {
+            Bonus::Missed => None,
+            Bonus::Spelled { common } => {
+                Some((raw_bonus_word_score(board, wildcard_index, puzzle), common))
+            }
+        }
+    }
+}
+
+/// What the bonus path of `board` spells, going by which of `bonus_words` it
+/// matches. Nothing, until the rows along the path are filled.
+pub fn bonus_of(board: &[&PackedWord], bonus_words: &[&PackedWord], puzzle: &Puzzle) -> Bonus {
+    let spelled = bonus_words.iter().find(|bonus_word| {
+        puzzle
+            .bonus_path
+            .iter()
+            .zip(bonus_word.letters())
+            .all(|(&(r, c), &letter)| board.get(r).is_some_and(|w| w.letters[c] == letter))
+    });
+    match spelled {
+        Some(word) => Bonus::Spelled {
+            common: word.common,
+        },
+        None => Bonus::Missed,
+    }
+}
+
+/// Where a board's score comes from, with the wildcard where `score_board`
+/// puts it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoreBreakdown {
+    /// Cell the wildcard goes in, if the board uses it.
+    pub wildcard: Option<(usize, usize)>,
+    /// Each row word's score on its own.
+    pub rows: Vec<u32>,
+    /// The bonus word's score on its own, if it was spelled.
+    pub bonus: Option<u32>,
+    /// The board's score. With per-board rounding it can differ from the sum of
+    /// the parts, which are rounded one by one.
+    pub total: u32,
+}
+
+/// Breaks a board's score down by row, with the wildcard in `wildcard_index`.
+pub fn score_breakdown_at(
+    board: &[&PackedWord],
+    bonus: Bonus,
+    wildcard_index: Option<(usize, usize)>,
+    puzzle: &Puzzle,
+) -> ScoreBreakdown {
+    let raw_rows = board
+        .iter()
+        .enumerate()
+        .map(|(row, word)| {
+            (
+                raw_word_score(row, word, wildcard_index, puzzle),
+                word.common,
+            )
+        })
+        .collect::<Vec<_>>();
+    let raw_bonus = bonus.raw_score(board, wildcard_index, puzzle);
+    ScoreBreakdown {
+        wildcard: wildcard_index,
+        rows: raw_rows
+            .iter()
+            .map(|&(raw, common)| puzzle.word_score(raw, common))
+            .collect(),
+        bonus: raw_bonus.map(|raw| puzzle.board_score([], Some(raw))),
+        total: puzzle.board_score(raw_rows, raw_bonus),
+    }
+}
+
+/// Breaks `score_board`'s result down by row.
+pub fn score_breakdown(board: &[&PackedWord], bonus: Bonus, puzzle: &Puzzle) -> ScoreBreakdown {
+    let breakdown = |wildcard_index| score_breakdown_at(board, bonus, wildcard_index, puzzle);
+    let Some(wildcard_letter) = board.iter().filter_map(|w| w.wildcard).next_back() else {
+        return breakdown(None);
+    };
+    board
+        .iter()
+        .enumerate()
+        .filter_map(|(row, word)| word.position_of(wildcard_letter).map(|col| (row, col)))
+        .map(|wildcard_index| breakdown(Some(wildcard_index)))
+        // The first of equally good placements, like `score_board`
+        .reduce(|best, b| if b.total > best.total { b } else { best })
+        .expect("the word that needs the wildcard has its letter")
+}
+
+pub fn score_board(board: &[&PackedWord], bonus: Bonus, puzzle: &Puzzle) -> u32 {
+    let board_score = |wildcard_index| {
+        let rows = board.iter().enumerate().map(|(row, word)| {
+            (
+                raw_word_score(row, word, wildcard_index, puzzle),
+                word.common,
+            )
+        });
+        puzzle.board_score(rows, bonus.raw_score(board, wildcard_index, puzzle))
+    };
+    // If wildcard is used, we can use any letter in its place
+    let Some(wildcard_letter) = board.iter().filter_map(|w| w.wildcard).next_back() else {
+        return board_score(None);
+    };
+    // Now, try calculating the score where wildcard is used in each place that letter is used
+    board
+        .iter()
+        .enumerate()
+        .filter_map(|(row, word)| word.position_of(wildcard_letter).map(|col| (row, col)))
+        .map(|wildcard_index| board_score(Some(wildcard_index)))
+        .max()
+        .unwrap_or(0)
+}
+
+fn search_from_bonus<'a>(
+    bonus_word: &PackedWord,
+    valid_words: &[&'a PackedWord],
+    fixed: &[Option<&'a PackedWord>; 5],
+    letter_bag: &LetterBag,
+    puzzle: &Puzzle,
+    board: &mut Board<'a>,
+    best: &mut (Board<'a>, u32),
+) {
+    let row = board.len();
+    if row > 4 {
+        let bonus = Bonus::Spelled {
+            common: bonus_word.common,
+        };
+        let score = score_board(board, bonus, puzzle);
+        if score > best.1 {
+            *best = (board.clone(), score);
+        }
+        return;
+    }
+    // The path can cross a row more than once, or not at all
+    let spells_bonus = |word: &PackedWord| {
+        puzzle
+            .bonus_path
+            .iter()
+            .zip(bonus_word.letters())
+            .all(|(&(r, c), &letter)| r != row || word.letters[c] == letter)
+    };
+
+    // A row that's already filled in only has to fit
+    if let Some(word) = fixed[row] {
+        if let Some(bag) = remove_word_from_bag(word, letter_bag)
+            && spells_bonus(word)
+        {
+            board.push(word);
+            search_from_bonus(bonus_word, valid_words, fixed, &bag, puzzle, board, best);
+            board.pop();
+        }
+        return;
+    }
+
+    // Drop off other valid_words that are not valid for the current word_bag
+    let valid_words = valid_words
+        .iter()
+        .filter_map(|&w| remove_word_from_bag(w, letter_bag).map(|b| (w, b)))
+        .collect::<Vec<_>>();
+    let valid_words_vec = valid_words.iter().map(|&(w, _)| w).collect::<Vec<_>>();
+
+    // The words are sorted by their last-row prescore, so the first one that fits is the best
+    if row == 4 {
+        if let Some(&(word, bag)) = valid_words.iter().find(|(w, _)| spells_bonus(w)) {
+            board.push(word);
+            search_from_bonus(
+                bonus_word,
+                &valid_words_vec,
+                fixed,
+                &bag,
+                puzzle,
+                board,
+                best,
+            );
+            board.pop();
+        }
+        return;
+    }
+
+    for &(word, bag) in &valid_words {
+        if !spells_bonus(word) {
+            continue;
+        }
+        board.push(word);
+        search_from_bonus(
+            bonus_word,
+            &valid_words_vec,
+            fixed,
+            &bag,
+            puzzle,
+            board,
+            best,
+        );
+        board.pop();
+    }
+}
+
+/// Finds the highest scoring board whose bonus path spells `bonus_word`.
+pub fn best_board_from_bonus<'a>(
+    bonus_word: &PackedWord,
+    valid_words: &[&'a PackedWord],
+    letter_bag: &LetterBag,
+    puzzle: &Puzzle,
+) -> (Board<'a>, u32) {
+    let mut best = (vec![], 0);
+    search_from_bonus(
+        bonus_word,
+        valid_words,
+        &[None; 5],
+        letter_bag,
+        puzzle,
+        &mut Vec::with_capacity(5),
+        &mut best,
+    );
+    best
+}
+
+/// Finds the highest scoring board that keeps the `fixed` rows and spells one
+/// of `bonus_words` along the bonus path, filling the other rows from
+/// `valid_words`. Returns an empty board and 0 if no such board exists, or if
+/// `cancelled` is set before the search is done.
+pub fn best_completion<'a>(
+    fixed: &[Option<&'a PackedWord>; 5],
+    valid_words: &[&'a PackedWord],
+    bonus_words: &[&PackedWord],
+    puzzle: &Puzzle,
+    cancelled: &AtomicBool,
+) -> (Board<'a>, u32) {
+    let letter_bag = puzzle.letter_bag();
+    let results = bonus_words
+        .par_iter()
+        .map(|bonus_word| {
+            let mut best = (vec![], 0);
+            if cancelled.load(Ordering::Relaxed) {
+                return best;
+            }
+            search_from_bonus(
+                bonus_word,
+                valid_words,
+                fixed,
+                &letter_bag,
+                puzzle,
+                &mut Vec::with_capacity(5),
+                &mut best,
+            );
+            best
+        })
+        .collect();
+    if cancelled.load(Ordering::Relaxed) {
+        return (vec![], 0);
+    }
+    best_of(results)
+}
+
+/// Keeps the words that can be spelled from the bag, noting which letter (if any)
+/// has to come from the wildcard. The words are all packed as common ones.
+pub fn find_valid_words(lines: &[String], puzzle: &Puzzle) -> Vec<PackedWord> {
+    let letter_bag = puzzle.letter_bag();
+    lines
+        .iter()
+        .filter_map(|word| {
+            if word.chars().count() > MAX_WORD_LEN {
+                return None;
+            }
+            let mut bag = letter_bag;
+            let mut wildcard_char: Option<char> = None;
+            for c in word.chars() {
+                let i = puzzle.alphabet.index_of(c)?;
+                if bag[i] > 0 {
+                    bag[i] -= 1;
+                } else if wildcard_char.is_none() && bag[WILDCARD] > 0 {
+                    wildcard_char = Some(c);
+                    bag[WILDCARD] -= 1;
+                } else {
+                    return None;
+                }
+            }
+            // If we reach here, the word is valid
+            Some(PackedWord::new(word, wildcard_char, puzzle))
+        })
+        .collect()
+}
+
+/// `find_valid_words` over the words of `dictionary` that fit the puzzle's bag,
+/// each common or not as the dictionary says.
+pub fn find_valid_dictionary_words(
+    dictionary: &dyn Dictionary,
+    puzzle: &Puzzle,
+) -> Vec<PackedWord> {
+    let words = dictionary.words_fitting(&puzzle.letter_bag(), &puzzle.alphabet);
+    let mut valid_words = find_valid_words(&words, puzzle);
+    for word in &mut valid_words {
+        word.common = dictionary.is_common(&word.word);
+    }
+    valid_words
+}
+
+/// Splits the valid words into bonus word candidates and 5 letter rows, with the
+/// rows sorted by descending last-row prescore as `solve` expects.
+pub fn split_valid_words<'a>(
+    valid_words: &'a [PackedWord],
+    puzzle: &Puzzle,
+) -> (Vec<&'a PackedWord>, Vec<&'a PackedWord>) {
+    let bonus_words = valid_words
+        .iter()
+        .filter(|w| w.len == puzzle.bonus_path.len())
+        .collect();
+    // Every row spans the whole grid, so shorter words only fit the bonus path
+    let mut rows: Vec<&PackedWord> = valid_words
+        .iter()
+        .filter(|w| w.len == MAX_WORD_LEN)
+        .collect();
+    rows.sort_by_key(|&w| std::cmp::Reverse(prescore_word_in_row(4, w, puzzle)));
+    (bonus_words, rows)
+}
+
+/// Finds the best board for every bonus word, in parallel.
+///
+/// `valid_words` should already be sorted by descending row-4 prescore, since the
+/// last row is filled greedily. `on_progress` is called from the worker threads
+/// once per finished bonus word.
+pub fn solve<'a, F>(
+    valid_words: &[&'a PackedWord],
+    bonus_words: &[&PackedWord],
+    puzzle: &Puzzle,
+    on_progress: F,
+) -> Vec<(Board<'a>, u32)>
+where
+    F: Fn(&Progress) + Sync,
+{
+    let letter_bag = puzzle.letter_bag();
+    let counter = ProgressCounter::new(bonus_words.len());
+    bonus_words
+        .par_iter()
+        .map(|bonus_word| {
+            let result = best_board_from_bonus(bonus_word, valid_words, &letter_bag, puzzle);
+            on_progress(&counter.tick());
+            result
+        })
+        .collect()
+}
+
+/// How to search for the best board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Fix the bonus word first and fill the rows around it.
+    BonusFirst,
+    /// Try every board; only practical on small word lists.
+    BruteForce,
+}
+
+impl FromStr for Strategy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bonus-first" => Ok(Strategy::BonusFirst),
+            "brute-force" => Ok(Strategy::BruteForce),
+            _ => Err(Error::Parse(format!(
+                "unknown strategy `{s}`, expected `bonus-first` or `brute-force`"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Strategy::BonusFirst => "bonus-first",
+            Strategy::BruteForce => "brute-force",
+        })
+    }
+}
+
+impl Strategy {
+    pub fn solve<'a, F>(
+        self,
+        valid_words: &[&'a PackedWord],
+        bonus_words: &[&PackedWord],
+        puzzle: &Puzzle,
+        on_progress: F,
+    ) -> Vec<(Board<'a>, u32)>
+    where
+        F: Fn(&Progress) + Sync,
+    {
+        match self {
+            Strategy::BonusFirst => solve(valid_words, bonus_words, puzzle, on_progress),
+            Strategy::BruteForce => {
+                crate::brute_force::solve(valid_words, bonus_words, puzzle, on_progress)
+            }
+        }
+    }
+}
+
+/// The highest scoring board out of a strategy's results.
+pub fn best_of<'a>(scored_sets: Vec<(Board<'a>, u32)>) -> (Board<'a>, u32) {
+    scored_sets
+        .into_iter()
+        .max_by_key(|(_, score)| *score)
+        .unwrap_or_default()
+}
+
+/// The `k` highest scoring boards out of a strategy's results, best first.
+/// Boards that score nothing are left out.
+pub fn top_boards<'a>(mut scored_sets: Vec<(Board<'a>, u32)>, k: usize) -> Vec<(Board<'a>, u32)> {
+    scored_sets.retain(|(_, score)| *score > 0);
+    scored_sets.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    scored_sets.truncate(k);
+    scored_sets
+}
+
+/// Runs the whole pipeline on a word list: packs the words that fit the bag,
+/// searches with `strategy` and returns the best board's rows and its score.
+pub fn solve_word_list<F>(
+    words: &[String],
+    puzzle: &Puzzle,
+    strategy: Strategy,
+    on_progress: F,
+) -> (Vec<PackedWord>, u32)
+where
+    F: Fn(&Progress) + Sync,
+{
+    solve_valid_words(
+        &find_valid_words(words, puzzle),
+        puzzle,
+        strategy,
+        on_progress,
+    )
+}
+
+/// `solve_word_list` over the words of `dictionary` that fit the puzzle's bag,
+/// scoring each as common or not as the dictionary says.
+pub fn solve_dictionary<F>(
+    dictionary: &dyn Dictionary,
+    puzzle: &Puzzle,
+    strategy: Strategy,
+    on_progress: F,
+) -> (Vec<PackedWord>, u32)
+where
+    F: Fn(&Progress) + Sync,
+{
+    let valid_words = find_valid_dictionary_words(dictionary, puzzle);
+    solve_valid_words(&valid_words, puzzle, strategy, on_progress)
+}
+
+fn solve_valid_words<F>(
+    valid_words: &[PackedWord],
+    puzzle: &Puzzle,
+    strategy: Strategy,
+    on_progress: F,
+) -> (Vec<PackedWord>, u32)
+where
+    F: Fn(&Progress) + Sync,
+{
+    let (bonus_words, rows) = split_valid_words(valid_words, puzzle);
+    let (board, score) = best_of(strategy.solve(&rows, &bonus_words, puzzle, on_progress));
+    (board.into_iter().cloned().collect(), score)
+}
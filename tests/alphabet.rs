@@ -0,0 +1,109 @@
+use bongo_game::alphabet::Alphabet;
+use bongo_game::dictionary::{Diacritics, LoadOptions, normalize_word, read_words};
+use bongo_game::packed::PackedWord;
+use bongo_game::puzzle::Puzzle;
+use bongo_game::solver::{Strategy, solve_word_list};
+
+fn spanish() -> Alphabet {
+    "ABCDEFGHIJKLMNÑOPQRSTUVWXYZ".parse().unwrap()
+}
+
+/// Five NIÑOS rows, with NIÑO down the diagonal.
+const SPANISH_PUZZLE: &str = "
+alphabet = ABCDEFGHIJKLMNÑOPQRSTUVWXYZ
+letters = N:5:10 I:5:5 Ñ:5:40 O:5:5 S:5:5 *:1:0
+schema = 1 1 1 1 1 / 1 1 1 1 1 / 1 1 2 1 1 / 1 1 1 1 1 / 1 1 1 1 1
+bonus = 0,0 1,1 2,2 3,3
+";
+
+#[test]
+fn alphabets_parse_and_print() {
+    assert_eq!(Alphabet::latin().to_string(), "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+    let alphabet = spanish();
+    assert_eq!(alphabet.len(), 27);
+    assert_eq!(alphabet.index_of('Ñ'), Some(14));
+    assert_eq!(alphabet.index_of('O'), Some(15));
+    assert_eq!(alphabet.letter(14), 'Ñ');
+    assert!("ABCA".parse::<Alphabet>().is_err());
+    assert!("ABc".parse::<Alphabet>().is_err());
+    assert!("AB1".parse::<Alphabet>().is_err());
+}
+
+#[test]
+fn puzzle_alphabet_defaults_to_latin_plus_its_tiles() {
+    let puzzle: Puzzle = "letters = Ä:1:10 B:2:5 Ø:1:30 *:1:0
+schema = 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1
+bonus = 0,0 1,1 2,2 3,3"
+        .parse()
+        .unwrap();
+    assert_eq!(puzzle.alphabet.to_string(), "ABCDEFGHIJKLMNOPQRSTUVWXYZÄØ");
+    let reparsed: Puzzle = puzzle.to_string().parse().unwrap();
+    assert_eq!(reparsed.alphabet, puzzle.alphabet);
+    assert_eq!(reparsed.letter_bag(), puzzle.letter_bag());
+
+    // Packed words count letters by their index in the puzzle's alphabet
+    let word = PackedWord::new("ØBÄBA", None, &puzzle);
+    assert_eq!(word.counts.len(), 28);
+    assert_eq!((word.counts[0], word.counts[1]), (1, 2));
+    assert_eq!(&word.counts[26..], [1, 1]);
+
+    let err = "alphabet = ABC
+letters = Ä:1:10 *:1:0
+schema = 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1
+bonus = 0,0 1,1 2,2 3,3"
+        .parse::<Puzzle>()
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("'Ä' isn't in the alphabet"),
+        "{err}"
+    );
+}
+
+#[test]
+fn letters_in_the_alphabet_are_not_folded() {
+    let normalize = |word, alphabet| normalize_word(word, Diacritics::Fold, &alphabet).unwrap();
+    assert_eq!(
+        normalize("año", spanish()),
+        Some(("AÑO".to_string(), false))
+    );
+    // Decomposed input is composed first
+    assert_eq!(
+        normalize("an\u{303}o", spanish()),
+        Some(("AÑO".to_string(), false))
+    );
+    assert_eq!(
+        normalize("año", Alphabet::latin()),
+        Some(("ANO".to_string(), true))
+    );
+    // Accents the alphabet doesn't have are still folded
+    assert_eq!(
+        normalize("café", spanish()),
+        Some(("CAFE".to_string(), true))
+    );
+
+    let nordic = Alphabet::latin().with(['Æ', 'Ø', 'Å']).unwrap();
+    let options = LoadOptions {
+        alphabet: nordic,
+        ..LoadOptions::default()
+    };
+    let list = read_words("smørrebrød\nKÅL\n".as_bytes(), "da.txt", options).unwrap();
+    assert_eq!(list.words, ["SMØRREBRØD", "KÅL"]);
+    assert_eq!(
+        list.stats.by_length.into_iter().collect::<Vec<_>>(),
+        [(3, 1), (10, 1)]
+    );
+}
+
+#[test]
+fn puzzles_solve_in_their_own_alphabet() {
+    let puzzle: Puzzle = SPANISH_PUZZLE.parse().unwrap();
+    let words = ["NIÑOS", "NIÑO", "NINOS", "SONSO"].map(String::from);
+    let (board, score) = solve_word_list(&words, &puzzle, Strategy::BruteForce, |_| {});
+    assert_eq!(
+        board.iter().map(|w| w.word.as_str()).collect::<Vec<_>>(),
+        ["NIÑOS"; 5]
+    );
+    // Rows of 65, or 105 with the Ñ doubled in the middle row, and the bonus
+    // word's 10 + 5 + 80 + 5, each x1.3 rounded up
+    assert_eq!(score, 4 * 85 + 137 + 130);
+}
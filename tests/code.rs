@@ -0,0 +1,171 @@
+mod common;
+
+use bongo_game::Error;
+use bongo_game::code::{decode, encode};
+use bongo_game::puzzle::Puzzle;
+use common::fixture;
+
+fn round_trip(puzzle: &Puzzle) {
+    let code = encode(puzzle);
+    let decoded = decode(&code).unwrap_or_else(|e| panic!("{code}: {e}"));
+    assert_eq!(decoded.to_string(), puzzle.to_string());
+}
+
+#[test]
+fn codes_round_trip() {
+    round_trip(&Puzzle::default());
+    for name in ["bongo-gbm.puzzle", "bongo-bhm.puzzle"] {
+        round_trip(&fixture(name).parse().unwrap());
+    }
+    let spanish: Puzzle = "id = 2024-05-01 día
+alphabet = ABCDEFGHIJKLMNÑOPQRSTUVWXYZ
+letters = N:5:10 I:5:5 Ñ:5:40 O:5:5 S:5:5 *:1:0
+schema = 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1
+bonus = 0,0 1,1 2,2 3,3
+multiplier = 3/2
+rounding = nearest per-board"
+        .parse()
+        .unwrap();
+    round_trip(&spanish);
+    // Multipliers and scores too big to pack into a nibble or a byte
+    let big: Puzzle = "letters = Q:1:1000 Z:2:300 *:2:0
+schema = 20 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 1 / 1 1 1 1 999
+bonus = 4,4 3,3 2,2 1,1
+multiplier = 7
+rounding = floor"
+        .parse()
+        .unwrap();
+    round_trip(&big);
+}
+
+#[test]
+fn codes_are_short_and_forgiving() {
+    let code = encode(&Puzzle::default());
+    assert!(code.len() < 120, "{} characters", code.len());
+    assert!(code.chars().all(|c| c.is_ascii_alphanumeric()));
+    let expected = Puzzle::default().to_string();
+    // Lower case, grouped with dashes and spaces
+    let grouped = code
+        .to_lowercase()
+        .as_bytes()
+        .chunks(8)
+        .map(|chunk| String::from_utf8(chunk.to_vec()).unwrap())
+        .collect::<Vec<_>>()
+        .join("- ");
+    assert_eq!(decode(&grouped).unwrap().to_string(), expected);
+    // O and I read as 0 and 1
+    let look_alikes = code.replace('0', "O").replace('1', "I");
+    assert_eq!(decode(&look_alikes).unwrap().to_string(), expected);
+}
+
+#[test]
+fn damaged_codes_are_rejected() {
+    let code = encode(&fixture("bongo-gbm.puzzle").parse().unwrap());
+    for len in 0..code.len() {
+        match decode(&code[..len]) {
+            Err(Error::Parse(message)) => assert!(message.starts_with("bad puzzle code")),
+            other => panic!("{len} characters: {other:?}"),
+        }
+    }
+    for i in 0..code.len() {
+        let mut typo = code.clone().into_bytes();
+        typo[i] = if typo[i] == b'7' { b'8' } else { b'7' };
+        assert!(decode(std::str::from_utf8(&typo).unwrap()).is_err());
+    }
+    let err = decode(&format!("{code}U")).unwrap_err().to_string();
+    assert!(err.contains("'U' isn't a code character"), "{err}");
+    let err = decode(&code[..code.len() - 4]).unwrap_err().to_string();
+    assert!(err.contains("checksum"), "{err}");
+    // Version 2 in the first byte
+    let err = decode(&format!("08{}", &code[2..]))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("version 2"), "{err}");
+}
+
+#[test]
+fn codes_with_overflowing_scores_are_rejected() {
+    let mut puzzle = Puzzle::default();
+    puzzle.letters[0].score = u32::MAX;
+    let err = decode(&encode(&puzzle)).unwrap_err().to_string();
+    assert!(err.contains("more than the most a letter can"), "{err}");
+
+    let mut puzzle = Puzzle::default();
+    puzzle.common_multiplier.num = 1 << 20;
+    let err = decode(&encode(&puzzle)).unwrap_err().to_string();
+    assert!(err.contains("can't have terms over"), "{err}");
+    let text = Puzzle::default()
+        .to_string()
+        .replace("multiplier = 13/10", "multiplier = 13/1000");
+    assert!(text.parse::<Puzzle>().is_err());
+}
+
+/// A code for the raw `body`, with its checksum, for codes `encode` wouldn't
+/// write.
+fn code_for(body: &[u8]) -> String {
+    let mut crc = 0xffff_u16;
+    for &byte in body {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    let mut bytes = body.to_vec();
+    bytes.extend(crc.to_be_bytes());
+    let digits = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    let mut code = String::new();
+    let (mut buffer, mut bits) = (0_u32, 0);
+    for byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            code.push(digits[(buffer >> bits) as usize & 31] as char);
+        }
+    }
+    if bits > 0 {
+        code.push(digits[(buffer << (5 - bits)) as usize & 31] as char);
+    }
+    code
+}
+
+#[test]
+fn varints_past_32_bits_are_rejected() {
+    // Version 1, ceil rounding with a packed schema, 25 A tiles worth 5, a
+    // flat schema, the default bonus path and the multiplier's numerator, 13.
+    // Only its denominator is left to add
+    let mut body = vec![1, 0x14, 1, 0, 25, 5];
+    body.extend([0x11; 12]);
+    body.extend([0x10, 2, 7, 12, 18, 13]);
+    let den = |bytes: &[u8]| code_for(&[body.as_slice(), bytes].concat());
+
+    let puzzle = decode(&den(&[10])).unwrap();
+    assert_eq!(puzzle.common_multiplier.den, 10);
+    // 10 padded out to five bytes
+    assert_eq!(
+        decode(&den(&[0x8a, 0x80, 0x80, 0x80, 0x00]))
+            .unwrap()
+            .common_multiplier
+            .den,
+        10
+    );
+    // The same with a bit set past the 32nd, which used to be dropped
+    let err = decode(&den(&[0x8a, 0x80, 0x80, 0x80, 0x10]))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("a number is too large"), "{err}");
+}
+
+#[test]
+fn puzzles_with_too_many_letter_entries_are_rejected() {
+    let mut puzzle = Puzzle::default();
+    let letter = puzzle.letters[0].clone();
+    puzzle.letters.resize(256, letter);
+    let err = puzzle.check_limits().unwrap_err().to_string();
+    assert!(err.contains("256 letter entries"), "{err}");
+    assert!(puzzle.to_string().parse::<Puzzle>().is_err());
+}
@@ -0,0 +1,92 @@
+// Each test binary uses its own subset of these helpers
+#![allow(dead_code)]
+
+use bongo_game::dictionary::{LoadOptions, MemoryDictionary, Strictness, load_words};
+use bongo_game::packed::PackedWord;
+use bongo_game::puzzle::Puzzle;
+use bongo_game::report::{SolveReport, SolveStats};
+use bongo_game::solver::{Board, find_valid_words, solve, split_valid_words, top_boards};
+use std::fs;
+use std::path::PathBuf;
+
+pub fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name)
+}
+
+pub fn fixture(name: &str) -> String {
+    let path = fixture_path(name);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+}
+
+/// One of the full word lists kept at the top of the repo.
+pub fn word_list_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(name)
+}
+
+/// A path in the temp directory unique to this test run, with any file or
+/// directory left behind by an earlier run cleared away.
+pub fn scratch_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("bongo-{}-{name}", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let _ = fs::remove_dir_all(&path);
+    path
+}
+
+/// The gbm puzzle with its word list, for tests that play rather than solve.
+pub fn gbm() -> (Puzzle, MemoryDictionary) {
+    let puzzle = fixture("bongo-gbm.puzzle").parse().unwrap();
+    let words = fixture("bongo-gbm-words.txt");
+    (
+        puzzle,
+        MemoryDictionary::new(words.lines().map(String::from)),
+    )
+}
+
+/// Reads a puzzle fixture and the words from `dictionary` that fit its bag.
+pub fn load(puzzle: &str, dictionary: &str) -> (Puzzle, Vec<PackedWord>) {
+    let puzzle: Puzzle = fixture(puzzle).parse().unwrap();
+    let words = load_words(
+        fixture_path(dictionary),
+        LoadOptions {
+            strictness: Strictness::Reject,
+            ..LoadOptions::default()
+        },
+    )
+    .unwrap();
+    let valid_words = find_valid_words(&words.words, &puzzle);
+    (puzzle, valid_words)
+}
+
+pub fn best_of(scored_sets: Vec<(Board<'_>, u32)>) -> (Vec<String>, u32) {
+    let (board, score) = scored_sets
+        .into_iter()
+        .max_by_key(|(_, score)| *score)
+        .unwrap_or_default();
+    (board.iter().map(|w| w.word.clone()).collect(), score)
+}
+
+/// Solves the gbm puzzle and hands `f` a report named `name` on its `top`
+/// boards, along with the boards and row candidates it was built from.
+pub fn with_gbm_report<T>(
+    name: &str,
+    top: usize,
+    elapsed_ms: u64,
+    f: impl FnOnce(SolveReport, &[(Board<'_>, u32)], &[&PackedWord], &Puzzle) -> T,
+) -> T {
+    let (puzzle, valid_words) = load("bongo-gbm.puzzle", "bongo-gbm-words.txt");
+    let (bonus_words, rows) = split_valid_words(&valid_words, &puzzle);
+    let scored_sets = solve(&rows, &bonus_words, &puzzle, |_| {});
+    let stats = SolveStats {
+        strategy: "bonus-first".to_string(),
+        dictionary_words: valid_words.len(),
+        bonus_candidates: bonus_words.len(),
+        row_candidates: rows.len(),
+        boards_found: scored_sets.len(),
+        elapsed_ms,
+    };
+    let boards = top_boards(scored_sets, top);
+    let report = SolveReport::new(name, stats, &boards, &bonus_words, &puzzle);
+    f(report, &boards, &rows, &puzzle)
+}
@@ -0,0 +1,167 @@
+use bongo_game::Error;
+use bongo_game::dictionary::{
+    Diacritics, LoadOptions, Pattern, Strictness, WordFilter, difference, intersect, load_words,
+    merge, read_words,
+};
+use bongo_game::error::BadLineReason;
+use std::io::Write;
+use std::path::Path;
+
+const MIXED: &str = "about\n  Tower \n\nrock'n\nbo4ts\ncafé\nZEBRA\nabout\nStraße\n";
+
+fn options(strictness: Strictness, diacritics: Diacritics) -> LoadOptions {
+    LoadOptions {
+        strictness,
+        diacritics,
+        ..LoadOptions::default()
+    }
+}
+
+#[test]
+fn lines_are_normalized_and_deduplicated() {
+    let list = read_words(MIXED.as_bytes(), "mixed.txt", LoadOptions::default()).unwrap();
+    assert_eq!(list.words, ["ABOUT", "TOWER", "CAFE", "ZEBRA", "STRASSE"]);
+    assert_eq!(list.stats.lines, 9);
+    assert_eq!(list.stats.blank, 1);
+    assert_eq!(list.stats.duplicates, 1);
+    assert_eq!(list.stats.folded, 1);
+    assert_eq!(list.stats.skipped, 2);
+    assert_eq!(
+        list.stats.by_length.into_iter().collect::<Vec<_>>(),
+        [(4, 1), (5, 3), (7, 1)]
+    );
+}
+
+#[test]
+fn bad_lines_are_skipped_with_their_location() {
+    let list = read_words(
+        MIXED.as_bytes(),
+        "mixed.txt",
+        options(Strictness::Skip, Diacritics::Drop),
+    )
+    .unwrap();
+    assert_eq!(list.words, ["ABOUT", "TOWER", "ZEBRA", "STRASSE"]);
+
+    let skipped = list
+        .skipped
+        .iter()
+        .map(|b| (b.line, b.text.as_str(), b.reason))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        skipped,
+        [
+            (4, "rock'n", BadLineReason::InvalidChar('\'')),
+            (5, "bo4ts", BadLineReason::InvalidChar('4')),
+            (6, "café", BadLineReason::InvalidChar('É')),
+        ]
+    );
+    assert_eq!(
+        list.skipped[0].to_string(),
+        "mixed.txt:4: `rock'n`: invalid character '\\''"
+    );
+}
+
+#[test]
+fn strict_loading_rejects_the_first_bad_line() {
+    let strict = options(Strictness::Reject, Diacritics::Fold);
+    match read_words(MIXED.as_bytes(), "mixed.txt", strict) {
+        Err(Error::BadLine(bad_line)) => {
+            assert_eq!(bad_line.path, Path::new("mixed.txt"));
+            assert_eq!(bad_line.line, 4);
+        }
+        other => panic!("expected a bad line, got {other:?}"),
+    }
+}
+
+#[test]
+fn missing_dictionary_names_the_file() {
+    let err = load_words("no-such-words.txt", LoadOptions::default()).unwrap_err();
+    assert!(matches!(err, Error::Io { .. }));
+    assert!(err.to_string().starts_with("no-such-words.txt: "), "{err}");
+}
+
+#[test]
+fn compressed_lists_are_detected_by_content() {
+    let mut gzip = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+    gzip.write_all(MIXED.as_bytes()).unwrap();
+    let gzip = gzip.finish().unwrap();
+    let zstd = zstd::encode_all(MIXED.as_bytes(), 0).unwrap();
+    let plain = read_words(MIXED.as_bytes(), "mixed.txt", LoadOptions::default()).unwrap();
+
+    // Named without an extension, to show it's the bytes that count
+    for (name, bytes) in [("gzip", gzip), ("zstd", zstd)] {
+        let path = std::env::temp_dir().join(format!("bongo-words-{}-{name}", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        let list = load_words(&path, LoadOptions::default()).unwrap();
+        assert_eq!(list.words, plain.words, "{name}");
+        assert_eq!(list.stats, plain.stats, "{name}");
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn corrupt_compressed_list_names_the_file() {
+    let path = std::env::temp_dir().join(format!("bongo-words-{}-corrupt.gz", std::process::id()));
+    std::fs::write(&path, [0x1f, 0x8b, 0x08, 0x00, 0xff, 0xff]).unwrap();
+    let err = load_words(&path, LoadOptions::default()).unwrap_err();
+    assert!(matches!(err, Error::Io { .. }));
+    assert!(
+        err.to_string().starts_with(&path.display().to_string()),
+        "{err}"
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+fn words(list: &[&str]) -> Vec<String> {
+    list.iter().map(|w| w.to_string()).collect()
+}
+
+#[test]
+fn set_operations_keep_first_list_order() {
+    let a = words(&["BONGO", "ABOUT", "TOWER"]);
+    let b = words(&["TOWER", "ZEBRA", "BONGO"]);
+    let c = words(&["BONGO"]);
+    assert_eq!(
+        merge([a.as_slice(), b.as_slice()]),
+        ["BONGO", "ABOUT", "TOWER", "ZEBRA"]
+    );
+    assert_eq!(intersect(&a, &[&b]), ["BONGO", "TOWER"]);
+    assert_eq!(intersect(&a, &[&b, &c]), ["BONGO"]);
+    assert_eq!(difference(&a, &[&b]), ["ABOUT"]);
+    assert_eq!(difference(&b, &[&a, &c]), ["ZEBRA"]);
+}
+
+#[test]
+fn patterns_match_single_letters_and_runs() {
+    let pattern: Pattern = "b?n*".parse().unwrap();
+    assert!(pattern.matches("BONGO"));
+    assert!(pattern.matches("BAN"));
+    assert!(!pattern.matches("BRING"));
+    assert!(!pattern.matches("BO"));
+
+    let pattern: Pattern = "*A*E".parse().unwrap();
+    assert!(pattern.matches("ABASE"));
+    assert!(pattern.matches("AE"));
+    assert!(!pattern.matches("ABASH"));
+
+    assert!("B-N".parse::<Pattern>().is_err());
+}
+
+#[test]
+fn filters_combine_length_pattern_and_membership() {
+    let list = words(&["BONGO", "BANG", "BINGO", "BAND", "BANDS"]);
+    let filter = WordFilter {
+        min_len: Some(4),
+        max_len: Some(4),
+        pattern: Some("BAN?".parse().unwrap()),
+        not_member_of: Some(["BAND".to_string()].into()),
+        ..WordFilter::default()
+    };
+    assert_eq!(filter.filter(&list), ["BANG"]);
+
+    let filter = WordFilter {
+        member_of: Some(["BINGO".to_string(), "BANDS".to_string()].into()),
+        ..WordFilter::default()
+    };
+    assert_eq!(filter.filter(&list), ["BINGO", "BANDS"]);
+}
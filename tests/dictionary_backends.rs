@@ -0,0 +1,154 @@
+mod common;
+
+use bongo_game::alphabet::Alphabet;
+use bongo_game::dictionary::{
+    Dictionary, FstDictionary, IndexDictionary, LoadOptions, MemoryDictionary, TextDictionary,
+};
+use bongo_game::packed::WILDCARD;
+use bongo_game::puzzle::LetterBag;
+use bongo_game::solver::{Strategy, solve_dictionary};
+use common::{fixture, fixture_path, scratch_path};
+
+fn fixture_dictionary() -> MemoryDictionary {
+    let mut dictionary = MemoryDictionary::default();
+    for (word, common) in [
+        ("BONGO", true),
+        ("BINGO", true),
+        ("AALII", false),
+        ("GO", true),
+        ("ZONKS", false),
+        ("ABACUSES", true),
+        ("A", false),
+    ] {
+        dictionary.insert(word, common);
+    }
+    dictionary
+}
+
+/// The fixture in every backend, each built from the one before.
+fn backends() -> Vec<(&'static str, Box<dyn Dictionary>)> {
+    let memory = fixture_dictionary();
+    let index =
+        IndexDictionary::from_bytes(IndexDictionary::compile(&memory).as_bytes().to_vec()).unwrap();
+    let fst =
+        FstDictionary::from_bytes(FstDictionary::compile(&index).as_bytes().to_vec()).unwrap();
+    vec![
+        ("memory", Box::new(memory)),
+        ("index", Box::new(index)),
+        ("fst", Box::new(fst)),
+    ]
+}
+
+fn sorted(words: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut words = words.into_iter().collect::<Vec<_>>();
+    words.sort();
+    words
+}
+
+fn bag(letters: &str, wildcards: u8) -> LetterBag {
+    let mut bag = [0; WILDCARD + 1];
+    for c in letters.chars() {
+        bag[c as usize - 'A' as usize] += 1;
+    }
+    bag[WILDCARD] = wildcards;
+    bag
+}
+
+#[test]
+fn backends_agree_on_lookups() {
+    for (name, dictionary) in backends() {
+        assert_eq!(dictionary.len(), 7, "{name}");
+        assert!(dictionary.contains("BONGO"), "{name}");
+        assert!(dictionary.contains("A"), "{name}");
+        assert!(!dictionary.contains("BONG"), "{name}");
+        assert!(!dictionary.contains("BONGOS"), "{name}");
+        assert!(!dictionary.contains(""), "{name}");
+        assert!(dictionary.is_common("BINGO"), "{name}");
+        assert!(!dictionary.is_common("ZONKS"), "{name}");
+        assert!(!dictionary.is_common("BONG"), "{name}");
+    }
+}
+
+#[test]
+fn backends_agree_on_iteration() {
+    for (name, dictionary) in backends() {
+        assert_eq!(
+            sorted(dictionary.words_of_length(5)),
+            ["AALII", "BINGO", "BONGO", "ZONKS"],
+            "{name}"
+        );
+        assert_eq!(
+            sorted(dictionary.words_of_length(3)),
+            [] as [&str; 0],
+            "{name}"
+        );
+        assert_eq!(
+            sorted(dictionary.words_fitting(&bag("BONGIX", 0), &Alphabet::latin())),
+            ["BINGO", "GO"],
+            "{name}"
+        );
+        assert_eq!(
+            sorted(dictionary.words_fitting(&bag("BONG", 1), &Alphabet::latin())),
+            ["A", "BINGO", "BONGO", "GO"],
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn compiled_dictionaries_round_trip_through_files() {
+    let dir = std::env::temp_dir();
+    let memory = fixture_dictionary();
+    let index_path = dir.join(format!("bongo-{}.idx", std::process::id()));
+    let fst_path = dir.join(format!("bongo-{}.fst", std::process::id()));
+    IndexDictionary::compile(&memory)
+        .write(&index_path)
+        .unwrap();
+    FstDictionary::compile(&memory).write(&fst_path).unwrap();
+
+    let index = IndexDictionary::open(&index_path).unwrap();
+    let fst = FstDictionary::open(&fst_path).unwrap();
+    assert_eq!(sorted(index.words()), sorted(memory.words()));
+    assert_eq!(sorted(fst.words()), sorted(memory.words()));
+    std::fs::remove_file(index_path).unwrap();
+    std::fs::remove_file(fst_path).unwrap();
+}
+
+#[test]
+fn corrupt_index_is_rejected() {
+    let bytes = IndexDictionary::compile(&fixture_dictionary())
+        .as_bytes()
+        .to_vec();
+    assert!(IndexDictionary::from_bytes(bytes[..bytes.len() - 1].to_vec()).is_err());
+    assert!(IndexDictionary::from_bytes(b"BONGO\n".to_vec()).is_err());
+    assert!(FstDictionary::from_bytes(b"BONGO\n".to_vec()).is_err());
+}
+
+#[test]
+fn text_dictionaries_keep_file_order_and_count_every_word_common() {
+    let path = scratch_path("text-dictionary.txt");
+    std::fs::write(&path, "ZONKS\nBONGO\nabacus\nBONGO\n").unwrap();
+    let text = TextDictionary::load(&path, LoadOptions::default()).unwrap();
+    assert_eq!(text.len(), 3);
+    assert_eq!(
+        text.words().collect::<Vec<_>>(),
+        ["ZONKS", "BONGO", "ABACUS"]
+    );
+    for word in ["ZONKS", "BONGO", "ABACUS"] {
+        assert!(text.contains(word) && text.is_common(word), "{word}");
+    }
+    assert!(!text.contains("BINGO") && !text.is_common("BINGO"));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn solver_gives_the_same_result_from_every_backend() {
+    let puzzle = fixture("bongo-gbm.puzzle").parse().unwrap();
+    let text =
+        TextDictionary::load(fixture_path("bongo-gbm-words.txt"), LoadOptions::default()).unwrap();
+    let index = IndexDictionary::compile(&text);
+    for dictionary in [&text as &dyn Dictionary, &index] {
+        let (_, score) = solve_dictionary(dictionary, &puzzle, Strategy::BonusFirst, |_| {});
+        assert_eq!(score, 1003);
+    }
+}
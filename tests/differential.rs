@@ -0,0 +1,92 @@
+//! Runs the bonus-first search and the brute-force reference solver on the
+//! same small word lists and checks they agree on the best score: the thinned
+//! fixture lists, and `sgb-words-mini.txt`.
+
+mod common;
+
+use bongo_game::dictionary::{LoadOptions, load_words};
+use bongo_game::packed::PackedWord;
+use bongo_game::puzzle::Puzzle;
+use bongo_game::solver::{Strategy, find_valid_words, split_valid_words};
+use common::{best_of, fixture, load, word_list_path};
+
+fn assert_strategies_agree(puzzle: &Puzzle, valid_words: &[PackedWord], keep_every: usize) -> u32 {
+    let valid_words = valid_words
+        .iter()
+        .step_by(keep_every)
+        .cloned()
+        .collect::<Vec<_>>();
+    let (bonus_words, rows) = split_valid_words(&valid_words, puzzle);
+    let (search_board, search_score) =
+        best_of(Strategy::BonusFirst.solve(&rows, &bonus_words, puzzle, |_| {}));
+    let (reference_board, reference_score) =
+        best_of(Strategy::BruteForce.solve(&rows, &bonus_words, puzzle, |_| {}));
+    assert_eq!(
+        search_score, reference_score,
+        "search found {search_board:?}, brute force found {reference_board:?}"
+    );
+    search_score
+}
+
+#[test]
+fn strategies_agree_on_thinned_lists() {
+    for keep_every in [2, 3] {
+        for (puzzle, dictionary) in [
+            ("bongo-gbm.puzzle", "bongo-gbm-words.txt"),
+            ("bongo-bhm.puzzle", "bongo-bhm-words.txt"),
+        ] {
+            let (puzzle, valid_words) = load(puzzle, dictionary);
+            assert_strategies_agree(&puzzle, &valid_words, keep_every);
+        }
+    }
+}
+
+#[test]
+fn strategies_agree_on_paths_through_the_last_row() {
+    let words = fixture("bongo-gbm-words.txt")
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    // Through row 4, and twice through the same row
+    for path in ["1,2 2,2 3,3 4,3", "0,1 0,3 2,2 4,4"] {
+        let puzzle: Puzzle = fixture("bongo-gbm.puzzle")
+            .replace("bonus = 0,2 1,2 2,2 3,3", &format!("bonus = {path}"))
+            .parse()
+            .unwrap();
+        assert_eq!(puzzle.bonus_path.last().unwrap().0, 4);
+        let valid_words = find_valid_words(&words, &puzzle);
+        for keep_every in [2, 3] {
+            assert_strategies_agree(&puzzle, &valid_words, keep_every);
+        }
+    }
+}
+
+/// The words of `sgb-words-mini.txt` that fit `puzzle`. The list only has five
+/// letter words, so the bonus words are taken from the puzzle's fixture list.
+fn mini_words(puzzle: &str, dictionary: &str) -> (Puzzle, Vec<PackedWord>) {
+    let puzzle: Puzzle = fixture(puzzle).parse().unwrap();
+    let mut words = load_words(word_list_path("sgb-words-mini.txt"), LoadOptions::default())
+        .unwrap()
+        .words;
+    let bonus_len = puzzle.bonus_path.len();
+    words.extend(
+        fixture(dictionary)
+            .lines()
+            .filter(|w| w.chars().count() == bonus_len)
+            .map(String::from),
+    );
+    let valid_words = find_valid_words(&words, &puzzle);
+    (puzzle, valid_words)
+}
+
+#[test]
+#[ignore = "brute force over the whole list takes minutes in debug; run with --ignored"]
+fn strategies_agree_on_sgb_words_mini() {
+    for (puzzle, dictionary, best) in [
+        ("bongo-gbm.puzzle", "bongo-gbm-words.txt", 1042),
+        ("bongo-bhm.puzzle", "bongo-bhm-words.txt", 838),
+    ] {
+        let (puzzle, valid_words) = mini_words(puzzle, dictionary);
+        assert_eq!(assert_strategies_agree(&puzzle, &valid_words, 1), best);
+    }
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use bongo_game::editor::{Editor, RowState};
+use bongo_game::solver::{find_valid_words, split_valid_words};
+use common::{fixture, gbm};
+use std::sync::atomic::AtomicBool;
+
+fn dictionary_words() -> Vec<String> {
+    fixture("bongo-gbm-words.txt")
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+fn type_word(editor: &mut Editor, word: &str) {
+    for c in word.chars() {
+        editor.type_letter(c);
+    }
+}
+
+#[test]
+fn typing_fills_the_row_under_the_cursor() {
+    let mut editor = Editor::new();
+    type_word(&mut editor, "batonx");
+    // The cursor stops at the end of the row, so the extra letter overwrites
+    assert_eq!(editor.word(0).as_deref(), Some("BATOX"));
+    assert_eq!(editor.cursor(), (0, 4));
+    editor.backspace();
+    editor.type_letter('N');
+    assert_eq!(editor.word(0).as_deref(), Some("BATON"));
+
+    editor.move_cursor(1, -5);
+    assert_eq!(editor.cursor(), (1, 0));
+    type_word(&mut editor, "br");
+    editor.backspace();
+    editor.backspace();
+    assert_eq!(editor.cursor(), (1, 0));
+    assert_eq!(editor.grid()[1], [None; 5]);
+    editor.move_cursor(-9, -9);
+    assert_eq!(editor.cursor(), (0, 0));
+}
+
+#[test]
+fn edits_can_be_undone_and_redone() {
+    let mut editor = Editor::new();
+    assert!(!editor.undo());
+    type_word(&mut editor, "ab");
+    editor.clear_row(0);
+    assert_eq!(editor.word(0), None);
+    assert!(editor.undo());
+    assert_eq!(editor.grid()[0][..2], [Some('A'), Some('B')]);
+    assert!(editor.undo());
+    assert!(editor.undo());
+    assert!(!editor.undo());
+    assert!(editor.redo());
+    assert_eq!(editor.grid()[0][0], Some('A'));
+
+    // A new edit drops what could have been redone
+    editor.type_letter('C');
+    assert!(!editor.redo());
+    // Clearing an empty row changes nothing, so there's nothing to undo for it
+    editor.clear_row(3);
+    assert!(editor.undo());
+    assert_eq!(editor.grid()[0], [Some('A'), None, None, None, None]);
+}
+
+#[test]
+fn rows_are_checked_and_tiles_counted_as_they_fill() {
+    let (puzzle, dictionary) = gbm();
+    let mut editor = Editor::new();
+    editor.fill(&["BATON".to_string(), "GRABS".to_string(), "LUM".to_string()]);
+    let (game, states) = editor.game(&puzzle, &dictionary);
+    assert_eq!(states[0], RowState::Placed);
+    assert!(matches!(&states[1], RowState::Rejected(why) if why.contains("dictionary")));
+    assert_eq!(states[2], RowState::Partial);
+    assert_eq!(states[3], RowState::Empty);
+    assert_eq!(game.score(), 120);
+
+    // Every letter typed counts, placed or not
+    let tiles = editor.tiles_left(&puzzle);
+    let left = |c| tiles.iter().find(|&&(t, _)| t == c).unwrap().1;
+    assert_eq!(left('B'), 1);
+    assert_eq!(left('G'), 0);
+    assert_eq!(left('M'), 0);
+    assert_eq!(left('*'), 1);
+    editor.fill(&[String::new(), String::new(), "LUMEM".to_string()]);
+    let tiles = editor.tiles_left(&puzzle);
+    assert_eq!(tiles.iter().find(|&&(t, _)| t == '*').unwrap().1, 0);
+}
+
+#[test]
+fn hints_complete_the_board_around_its_rows() {
+    let (puzzle, dictionary) = gbm();
+    let words = dictionary_words();
+    let valid_words = find_valid_words(&words, &puzzle);
+    let (bonus_words, rows) = split_valid_words(&valid_words, &puzzle);
+
+    let mut editor = Editor::new();
+    type_word(&mut editor, "BATON");
+    let (game, _) = editor.game(&puzzle, &dictionary);
+    let (board, score) = game.best_completion(&rows, &bonus_words).unwrap();
+    assert_eq!(board, ["BATON", "BROAD", "LUMEN", "STABS", "FRAGS"]);
+    assert_eq!(score, 1003);
+
+    // Keeping a row the best board doesn't have gives the best board with it
+    let mut editor = Editor::new();
+    type_word(&mut editor, "EMBER");
+    let (game, _) = editor.game(&puzzle, &dictionary);
+    let (board, score) = game.best_completion(&rows, &bonus_words).unwrap();
+    assert_eq!(board[0], "EMBER");
+    assert_eq!(score, 996);
+    editor.fill(&board);
+    assert_eq!(editor.game(&puzzle, &dictionary).0.score(), 996);
+
+    // A cancelled hint gives up
+    let cancelled = AtomicBool::new(true);
+    assert_eq!(
+        game.best_completion_unless(&rows, &bonus_words, &cancelled),
+        None
+    );
+}
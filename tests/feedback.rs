@@ -0,0 +1,146 @@
+mod common;
+
+use bongo_game::alphabet::Alphabet;
+use bongo_game::dictionary::{Dictionary, IndexDictionary, MemoryDictionary};
+use bongo_game::feedback::{FeedbackDictionary, FeedbackStore, Overlay, Verdict};
+use bongo_game::puzzle::Puzzle;
+use bongo_game::solver::{Strategy, solve_dictionary};
+use common::{gbm, scratch_path};
+/// `list` as owned strings.
+/// A fresh store path for one test, removed if a previous run left it behind.
+fn words(list: &[&str]) -> Vec<String> {
+    list.iter().map(|w| w.to_string()).collect()
+}
+
+#[test]
+fn missing_store_is_empty() {
+    let store = FeedbackStore::load(scratch_path("feedback-missing.txt")).unwrap();
+    assert_eq!(store.verdicts().count(), 0);
+}
+
+#[test]
+fn verdicts_persist_and_the_last_one_wins() {
+    let path = scratch_path("feedback-persist.txt");
+    let mut store = FeedbackStore::load(&path).unwrap();
+    store
+        .record("bongo", Verdict::Rejected, &Alphabet::latin())
+        .unwrap();
+    store
+        .record("  Café ", Verdict::Accepted, &Alphabet::latin())
+        .unwrap();
+    store
+        .record("BONGO", Verdict::Common, &Alphabet::latin())
+        .unwrap();
+
+    let reloaded = FeedbackStore::load(&path).unwrap();
+    assert_eq!(
+        reloaded.verdicts().collect::<Vec<_>>(),
+        [("BONGO", Verdict::Common), ("CAFE", Verdict::Accepted)]
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn overlay_blocks_rejected_and_allows_accepted_words() {
+    let path = scratch_path("feedback-overlay.txt");
+    let mut store = FeedbackStore::load(&path).unwrap();
+    store
+        .record("AALII", Verdict::Rejected, &Alphabet::latin())
+        .unwrap();
+    store
+        .record("ZONKS", Verdict::Accepted, &Alphabet::latin())
+        .unwrap();
+    store
+        .record("BINGO", Verdict::Common, &Alphabet::latin())
+        .unwrap();
+    store
+        .record("HELLO", Verdict::Accepted, &Alphabet::latin())
+        .unwrap();
+
+    let mut list = words(&["HELLO", "AALII", "WORLD"]);
+    let overlay = store.apply(&mut list);
+    assert_eq!(list, words(&["HELLO", "WORLD", "BINGO", "ZONKS"]));
+    assert_eq!(
+        overlay,
+        Overlay {
+            added: 2,
+            removed: 1
+        }
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn bad_store_lines_name_the_file_and_line() {
+    let path = scratch_path("feedback-bad.txt");
+    std::fs::write(&path, "rejected AALII\n\nmaybe ZONKS\n").unwrap();
+    let err = FeedbackStore::load(&path).unwrap_err().to_string();
+    assert!(err.contains(&format!("{}:3", path.display())), "{err}");
+    assert!(err.contains("unknown verdict `maybe`"), "{err}");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn verdicts_are_laid_over_compiled_dictionaries() {
+    let path = scratch_path("feedback-compiled.txt");
+    std::fs::write(
+        &path,
+        "rejected AALII\naccepted ZONKS\ncommon BINGO\ncommon HELLO\naccepted LUMEN\n",
+    )
+    .unwrap();
+    let mut base = MemoryDictionary::new(["AALII", "WORLD"]);
+    base.insert("HELLO", false);
+    base.insert("LUMEN", false);
+    let index = IndexDictionary::compile(&base);
+    let dictionary = FeedbackDictionary::new(Box::new(index), FeedbackStore::load(&path).unwrap());
+
+    assert_eq!(
+        dictionary.overlay(),
+        Overlay {
+            added: 2,
+            removed: 1
+        }
+    );
+    assert_eq!(dictionary.len(), 5);
+    assert!(!dictionary.contains("AALII"));
+    assert!(dictionary.contains("BINGO") && dictionary.is_common("BINGO"));
+    assert!(dictionary.contains("HELLO") && dictionary.is_common("HELLO"));
+    // Accepting a word says nothing about whether it's common
+    assert!(dictionary.contains("ZONKS") && !dictionary.is_common("ZONKS"));
+    assert!(dictionary.contains("LUMEN") && !dictionary.is_common("LUMEN"));
+    let mut words = dictionary.words_of_length(5).collect::<Vec<_>>();
+    words.sort();
+    assert_eq!(words, ["BINGO", "HELLO", "LUMEN", "WORLD", "ZONKS"]);
+    let bag = Puzzle::default().letter_bag();
+    let fitting = dictionary.words_fitting(&bag, &Alphabet::latin());
+    assert!(!fitting.contains(&"AALII".to_string()));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn common_verdicts_give_words_the_multiplier() {
+    let path = scratch_path("feedback-common.txt");
+    std::fs::write(&path, "common BATON\n").unwrap();
+    let (puzzle, mut base) = gbm();
+    // BATON is the first row of the best board, which falls to another board
+    // when BATON is uncommon
+    base.insert("BATON", false);
+    let dictionary = FeedbackDictionary::new(Box::new(base), FeedbackStore::load(&path).unwrap());
+    assert!(dictionary.is_common("BATON"));
+    let (board, score) = solve_dictionary(&dictionary, &puzzle, Strategy::BonusFirst, |_| {});
+    assert_eq!(score, 1003);
+    assert_eq!(board[0].word, "BATON");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn non_words_are_not_recorded() {
+    let path = scratch_path("feedback-nonword.txt");
+    let mut store = FeedbackStore::load(&path).unwrap();
+    assert!(
+        store
+            .record("B4NG", Verdict::Accepted, &Alphabet::latin())
+            .is_err()
+    );
+    assert!(!path.exists());
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use bongo_game::game::Game;
+use bongo_game::packed::WILDCARD;
+use common::gbm;
+
+#[test]
+fn rows_fill_up_and_score() {
+    let (puzzle, dictionary) = gbm();
+    let mut game = Game::new(&puzzle, &dictionary);
+    assert_eq!(game.next_empty_row(), Some(0));
+    assert_eq!(game.score(), 0);
+
+    game.place(0, "baton").unwrap();
+    game.place(3, "STABS").unwrap();
+    assert_eq!(game.word(0), Some("BATON"));
+    assert_eq!(game.next_empty_row(), Some(1));
+    assert_eq!(game.row_scores(), [Some(120), None, None, Some(241), None]);
+    assert_eq!(game.score(), 361);
+    assert_eq!(game.bonus_word(), None);
+
+    for (row, word) in [(1, "BROAD"), (2, "LUMEN"), (4, "FRAGS")] {
+        game.place(row, word).unwrap();
+    }
+    assert!(game.is_complete());
+    assert_eq!(game.bonus_word().as_deref(), Some("TOMB"));
+    assert!(game.bonus_word_used());
+    assert_eq!(game.score(), 1003);
+    assert_eq!(game.remaining()[WILDCARD], 0);
+}
+
+#[test]
+fn words_have_to_fit_the_bag_and_the_dictionary() {
+    let (puzzle, dictionary) = gbm();
+    let mut game = Game::new(&puzzle, &dictionary);
+    let err = game.place(0, "FUZZY").unwrap_err().to_string();
+    assert!(err.contains("no Z tiles left"), "{err}");
+    let err = game.place(0, "GRABS").unwrap_err().to_string();
+    assert!(err.contains("isn't in the dictionary"), "{err}");
+    let err = game.place(0, "BATONS").unwrap_err().to_string();
+    assert!(err.contains("isn't 5 letters long"), "{err}");
+    assert_eq!(game.next_empty_row(), Some(0));
+
+    // One F takes the wildcard, a second has nothing left
+    game.place(4, "FRAGS").unwrap();
+    let err = game.place(0, "FLAGS").unwrap_err().to_string();
+    assert!(err.contains("no F tiles left"), "{err}");
+}
+
+#[test]
+fn clearing_a_row_gives_its_tiles_back() {
+    let (puzzle, dictionary) = gbm();
+    let mut game = Game::new(&puzzle, &dictionary);
+    let bag = game.remaining();
+    game.place(0, "BROAD").unwrap();
+    game.place(1, "BROAD").unwrap();
+    assert_eq!(game.remaining()[WILDCARD], 0);
+
+    // Emptying the first row lets the second have real tiles again
+    assert_eq!(game.clear(0).as_deref(), Some("BROAD"));
+    assert_eq!(game.clear(0), None);
+    assert!(game.rows()[1].as_ref().unwrap().wildcard.is_none());
+    game.clear(1);
+    assert_eq!(game.remaining(), bag);
+}
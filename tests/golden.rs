@@ -0,0 +1,104 @@
+//! Golden tests: end-to-end solves of puzzles recorded from the game, against
+//! the word list it's played with, `bongo-common-words.txt`.
+//!
+//! Each case says where its puzzle was recorded from. No top score published
+//! by the game is on record for these puzzles yet, so `published_score` is
+//! empty and `best_score` is the solver's best on the full list from when the
+//! case was added, with its board rescored by `bongo_game share`. Once the
+//! game's score for a puzzle is known, set `published_score` and say where it
+//! came from, and it's checked too.
+//!
+//! Even in a release build, solving against the full list takes minutes for
+//! bhm and a couple of hours for gbm, so those cases are ignored by default;
+//! run them with `cargo test --release --test golden -- --ignored`. The
+//! thinned lists in `tests/golden/`, a cut of the full list's 4 and 5 letter
+//! words that fit each puzzle, keep a quick end-to-end check in every run.
+
+mod common;
+
+use bongo_game::dictionary::{LoadOptions, Strictness, TextDictionary};
+use bongo_game::puzzle::Puzzle;
+use bongo_game::solver::{Strategy, solve, solve_dictionary, split_valid_words};
+use common::{best_of, fixture, load, word_list_path};
+
+/// The list the game's puzzles are played with.
+const FULL_LIST: &str = "bongo-common-words.txt";
+
+struct Case {
+    puzzle: &'static str,
+    /// Where the puzzle was recorded from.
+    source: &'static str,
+    /// The game's published top score, if one is known.
+    published_score: Option<u32>,
+    /// The solver's best on the full list.
+    best_score: u32,
+    /// The thinned list in `tests/golden/` and the solver's best on it.
+    thinned: (&'static str, u32),
+}
+
+const CASES: [Case; 2] = [
+    Case {
+        puzzle: "bongo-gbm.puzzle",
+        source: "the puzzle hardcoded in the first version of src/main.rs",
+        published_score: None,
+        best_score: 1094,
+        thinned: ("bongo-gbm-words.txt", 1003),
+    },
+    Case {
+        puzzle: "bongo-bhm.puzzle",
+        source: "the puzzle left commented out in the first version of src/main.rs",
+        published_score: None,
+        best_score: 897,
+        thinned: ("bongo-bhm-words.txt", 734),
+    },
+];
+
+fn assert_full_list_best(case: &Case) {
+    let options = LoadOptions {
+        strictness: Strictness::Reject,
+        ..LoadOptions::default()
+    };
+    let dictionary = TextDictionary::load(word_list_path(FULL_LIST), options).unwrap();
+    let puzzle: Puzzle = fixture(case.puzzle).parse().unwrap();
+    let (board, score) = solve_dictionary(&dictionary, &puzzle, Strategy::BonusFirst, |_| {});
+    let words = board.iter().map(|w| w.word.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        score, case.best_score,
+        "{} ({}): {words:?}",
+        case.puzzle, case.source
+    );
+    if let Some(published) = case.published_score {
+        assert_eq!(score, published, "{}: the game's best", case.puzzle);
+    }
+}
+
+#[test]
+#[ignore = "takes a couple of hours; run with --release -- --ignored"]
+fn gbm_best_score_on_the_full_word_list() {
+    assert_full_list_best(&CASES[0]);
+}
+
+#[test]
+#[ignore = "takes minutes; run with --release -- --ignored"]
+fn bhm_best_score_on_the_full_word_list() {
+    assert_full_list_best(&CASES[1]);
+}
+
+#[test]
+fn best_scores_on_the_thinned_lists() {
+    for case in &CASES {
+        let (dictionary, best_score) = case.thinned;
+        let (puzzle, valid_words) = load(case.puzzle, dictionary);
+        let (bonus_words, rows) = split_valid_words(&valid_words, &puzzle);
+        let (board, score) = best_of(solve(&rows, &bonus_words, &puzzle, |_| {}));
+        assert_eq!(score, best_score, "{}: {board:?}", case.puzzle);
+    }
+}
+
+#[test]
+fn puzzle_files_round_trip() {
+    for case in &CASES {
+        let puzzle: Puzzle = fixture(case.puzzle).parse().unwrap();
+        assert_eq!(fixture(case.puzzle), puzzle.to_string());
+    }
+}
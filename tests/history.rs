@@ -0,0 +1,114 @@
+mod common;
+
+use bongo_game::history::{
+    Entry, append, civil_from_days, days_from_civil, load, parse_date, personal_best, stats,
+};
+use common::scratch_path;
+
+/// A fresh history path for one test, removed if a previous run left it behind.
+fn entry(date: &str, score: u32, seconds: u64) -> Entry {
+    Entry {
+        date: date.to_string(),
+        code: "CODE".to_string(),
+        board: vec!["BATON".to_string(), String::new()],
+        score,
+        seconds,
+        optimum: 1000,
+    }
+}
+
+#[test]
+fn dates_round_trip() {
+    assert_eq!(days_from_civil(1970, 1, 1), 0);
+    assert_eq!(civil_from_days(0), "1970-01-01");
+    assert_eq!(
+        parse_date("2024-03-01"),
+        Some(days_from_civil(2024, 2, 29) + 1)
+    );
+    for days in [-1, 59, 365, 11016, 19782, 20744] {
+        assert_eq!(parse_date(&civil_from_days(days)), Some(days));
+    }
+    assert_eq!(parse_date("2024-13-01"), None);
+    assert_eq!(parse_date("yesterday"), None);
+}
+
+#[test]
+fn days_past_the_end_of_the_month_are_rejected() {
+    assert_eq!(
+        parse_date("2024-02-29"),
+        Some(days_from_civil(2024, 3, 1) - 1)
+    );
+    assert_eq!(parse_date("2000-02-29"), Some(days_from_civil(2000, 2, 29)));
+    for date in [
+        "2024-02-30",
+        "2024-02-31",
+        "2023-02-29",
+        "1900-02-29",
+        "2024-04-31",
+    ] {
+        assert_eq!(parse_date(date), None, "{date}");
+    }
+    assert_eq!(
+        parse_date("2024-12-31"),
+        Some(days_from_civil(2025, 1, 1) - 1)
+    );
+}
+
+#[test]
+fn games_are_appended_and_read_back() {
+    let path = scratch_path("history-append.jsonl");
+    assert!(load(&path).unwrap().is_empty());
+    append(&path, &entry("2026-10-17", 500, 90)).unwrap();
+    append(&path, &entry("2026-10-18", 800, 60)).unwrap();
+    let entries = load(&path).unwrap();
+    assert_eq!(
+        entries,
+        [entry("2026-10-17", 500, 90), entry("2026-10-18", 800, 60)]
+    );
+    assert_eq!(personal_best(&entries, "CODE"), Some(800));
+    assert_eq!(personal_best(&entries, "OTHER"), None);
+
+    std::fs::write(&path, "{\"date\": 1}\n").unwrap();
+    let err = load(&path).unwrap_err().to_string();
+    assert!(err.contains(":1: "), "{err}");
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn streaks_and_averages() {
+    let entries = [
+        entry("2026-10-10", 400, 100),
+        entry("2026-10-11", 600, 200),
+        entry("2026-10-12", 500, 60),
+        entry("2026-10-16", 1000, 30),
+        entry("2026-10-17", 700, 110),
+        entry("2026-10-17", 800, 100),
+    ];
+    let summary = stats(&entries, "2026-10-18");
+    assert_eq!(summary.games, 6);
+    assert_eq!(summary.days, 5);
+    assert_eq!(summary.best_streak, 3);
+    // Not played yet today, so the streak runs to yesterday
+    assert_eq!(summary.current_streak, 2);
+    assert_eq!(summary.average_score, 4000.0 / 6.0);
+    assert_eq!(summary.average_percent, Some(400.0 / 6.0));
+    assert_eq!(summary.average_seconds, 100.0);
+    assert_eq!(summary.best, Some(entry("2026-10-16", 1000, 30)));
+
+    assert_eq!(stats(&entries, "2026-10-19").current_streak, 0);
+    assert_eq!(stats(&[], "2026-10-19").games, 0);
+}
+
+#[test]
+fn games_dated_after_today_dont_count_towards_the_current_streak() {
+    let entries = [
+        entry("2026-10-16", 400, 100),
+        entry("2026-10-17", 600, 200),
+        entry("2026-10-25", 500, 60),
+    ];
+    let summary = stats(&entries, "2026-10-18");
+    assert_eq!(summary.current_streak, 2);
+    assert_eq!(summary.best_streak, 2);
+    assert_eq!(stats(&entries[2..], "2026-10-18").current_streak, 0);
+    assert_eq!(stats(&entries, "2026-10-25").current_streak, 1);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use bongo_game::html::html_report;
+use bongo_game::report::{SolveReport, SolveStats};
+use common::{load, with_gbm_report};
+
+fn gbm_html(top: usize) -> String {
+    with_gbm_report("gbm <daily>", top, 42, |report, boards, rows, puzzle| {
+        html_report(&report, boards, rows, puzzle)
+    })
+}
+
+#[test]
+fn reports_cover_the_whole_solve() {
+    let html = gbm_html(3);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h1>gbm &lt;daily&gt;</h1>"));
+    // The puzzle definition, as it would be written to a file
+    assert!(html.contains("bonus = 0,2 1,2 2,2 3,3"));
+    // The best board and two alternatives, each drawn and broken down
+    assert_eq!(html.matches("<svg").count(), 3);
+    assert!(html.contains("<h3>#2 &middot; 996</h3>"));
+    assert!(html.contains("<td>Bonus</td><td>TOMB</td><td class=\"score\">263</td>"));
+    assert!(html.contains("<td>Wildcard</td><td>F at row 5, column 1</td>"));
+    assert!(html.contains("<td class=\"score\">1003</td>"));
+    // Candidates for each row, best first and capped
+    assert_eq!(html.matches("<th>Row ").count(), 5);
+    assert!(
+        html.contains("<tr><th>Row 1</th><th class=\"score\">363</th></tr>\n<tr><td>GUMBO</td>")
+    );
+    assert_eq!(html.matches("and 338 more").count(), 5);
+    assert!(html.contains("<td>Boards found</td><td class=\"score\">216</td>"));
+    assert!(html.contains("in 42 ms"));
+}
+
+#[test]
+fn reports_need_no_network() {
+    let html = gbm_html(2);
+    let without_namespaces = html.replace("xmlns=\"http://www.w3.org/2000/svg\"", "");
+    assert!(!without_namespaces.contains("http"));
+    assert!(!html.contains("<script"));
+    assert!(!html.contains("<link"));
+}
+
+#[test]
+fn reports_without_boards_say_so() {
+    let html = gbm_html(1);
+    assert!(!html.contains("Alternatives"));
+    let (puzzle, _) = load("bongo-gbm.puzzle", "bongo-gbm-words.txt");
+    let stats = SolveStats {
+        strategy: "brute-force".to_string(),
+        dictionary_words: 0,
+        bonus_candidates: 0,
+        row_candidates: 0,
+        boards_found: 0,
+        elapsed_ms: 0,
+    };
+    let report = SolveReport::new("empty", stats, &[], &[], &puzzle);
+    let html = html_report(&report, &[], &[], &puzzle);
+    assert!(html.contains("No valid board found."));
+    assert!(!html.contains("<svg"));
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use bongo_game::alphabet::Alphabet;
+use bongo_game::dictionary::{Affixes, Capitalized, LoadOptions, load_hunspell, read_hunspell};
+use common::scratch_path;
+
+/// A cut-down en_US affix file.
+const AFF: &str = "SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+NEEDAFFIX X
+FORBIDDENWORD !
+
+PFX U Y 1
+PFX U   0     un         .
+
+SFX D Y 4
+SFX D   0     d          e
+SFX D   y     ied        [^aeiou]y
+SFX D   0     ed         [^ey]
+SFX D   0     ed         [aeiou]y
+
+SFX G Y 2
+SFX G   e     ing        e
+SFX G   0     ing        [^e]
+
+SFX S Y 4
+SFX S   y     ies        [^aeiou]y
+SFX S   0     s          [aeiou]y
+SFX S   0     es         [sxzh]
+SFX S   0     s          [^sxzhy]
+";
+
+fn sorted(mut words: Vec<String>) -> Vec<String> {
+    words.sort();
+    words
+}
+
+fn expand(entry: &str) -> Vec<String> {
+    sorted(Affixes::parse(AFF).unwrap().expand(entry))
+}
+
+#[test]
+fn suffixes_follow_their_conditions() {
+    assert_eq!(expand("bake/DGS"), ["bake", "baked", "bakes", "baking"]);
+    assert_eq!(expand("cry/DS"), ["cried", "cries", "cry"]);
+    assert_eq!(expand("box/S"), ["box", "boxes"]);
+    assert_eq!(expand("walk"), ["walk"]);
+}
+
+#[test]
+fn cross_products_combine_prefixes_and_suffixes() {
+    assert_eq!(
+        expand("play/DSU"),
+        ["play", "played", "plays", "unplay", "unplayed", "unplays"]
+    );
+}
+
+#[test]
+fn stems_can_be_affix_only_or_forbidden() {
+    assert_eq!(expand("walk/XS"), ["walks"]);
+    assert_eq!(expand("zzz/!S"), [] as [&str; 0]);
+}
+
+#[test]
+fn long_and_numeric_flags() {
+    let long = Affixes::parse("FLAG long\nSFX Aa Y 1\nSFX Aa 0 s .\n").unwrap();
+    assert_eq!(sorted(long.expand("cat/Aa")), ["cat", "cats"]);
+    let num = Affixes::parse("FLAG num\nSFX 12 Y 1\nSFX 12 0 s .\n").unwrap();
+    assert_eq!(sorted(num.expand("cat/7,12")), ["cat", "cats"]);
+}
+
+#[test]
+fn dictionary_expands_into_a_clean_word_list() {
+    let dic = "5\nbake/DGS\nParis\nrock'n\ncafé/S\nbakes\n";
+    let affixes = Affixes::parse(AFF).unwrap();
+    let read = |capitalized| {
+        read_hunspell(dic, &affixes, "en.dic", capitalized, LoadOptions::default()).unwrap()
+    };
+    let list = read(Capitalized::Skip);
+    assert_eq!(
+        list.words,
+        ["BAKE", "BAKED", "BAKING", "BAKES", "CAFE", "CAFES"]
+    );
+    // The repeated BAKES is a duplicate, the proper noun never gets that far
+    assert_eq!(list.stats.duplicates, 1);
+    assert_eq!(list.stats.folded, 2);
+    assert_eq!(list.skipped.len(), 1);
+    assert_eq!(list.skipped[0].line, 4);
+    // German capitalizes every noun, so they can be kept
+    assert!(read(Capitalized::Keep).words.contains(&"PARIS".to_string()));
+}
+
+/// Writes a `.dic` and `.aff` pair for one test, loads it and removes it.
+fn load_pair(
+    name: &str,
+    aff: &[u8],
+    dic: &[u8],
+    options: LoadOptions,
+) -> bongo_game::Result<Vec<String>> {
+    let dic_path = scratch_path(&format!("{name}.dic"));
+    let aff_path = scratch_path(&format!("{name}.aff"));
+    std::fs::write(&aff_path, aff).unwrap();
+    std::fs::write(&dic_path, dic).unwrap();
+    let list = load_hunspell(&dic_path, &aff_path, Capitalized::Skip, options);
+    std::fs::remove_file(dic_path).unwrap();
+    std::fs::remove_file(aff_path).unwrap();
+    list.map(|list| list.words)
+}
+
+#[test]
+fn latin1_files_are_decoded() {
+    let words = load_pair(
+        "latin1",
+        b"SET ISO8859-1\nSFX S Y 1\nSFX S 0 s .\n",
+        b"1\ncaf\xe9/S\n",
+        LoadOptions::default(),
+    );
+    assert_eq!(words.unwrap(), ["CAFE", "CAFES"]);
+}
+
+#[test]
+fn other_declared_charsets_are_decoded() {
+    // 0xB3 is ł in ISO 8859-2, but ³ in Latin-1
+    let options = LoadOptions {
+        alphabet: Alphabet::latin().with(['Ł']).unwrap(),
+        ..LoadOptions::default()
+    };
+    let words = load_pair("latin2", b"SET ISO8859-2\n", b"1\n\xb3ad\n", options);
+    assert_eq!(words.unwrap(), ["ŁAD"]);
+    let russian = LoadOptions {
+        alphabet: Alphabet::new("ТОК".chars()).unwrap(),
+        ..LoadOptions::default()
+    };
+    let words = load_pair("koi8", b"SET KOI8-R\n", b"1\n\xd4\xcf\xcb\n", russian);
+    assert_eq!(words.unwrap(), ["ТОК"]);
+
+    let err = load_pair("iscii", b"SET ISCII-DEVANAGARI\n", b"1\nab\n", options).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("can't decode SET ISCII-DEVANAGARI"),
+        "{err}"
+    );
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use bongo_game::leaderboard::{DailyRank, Leaderboard, WeeklyTotal};
+use bongo_game::puzzle::Puzzle;
+use bongo_game::share::SharedBoard;
+use common::{fixture, gbm, scratch_path};
+
+/// A fresh leaderboard directory for one test, removed if a previous run left
+/// it behind.
+const BEST: &str = "BATON\nBROAD\nLUMEN\nSTABS\nFRAGS\n";
+const SECOND: &str = "EMBER\nBROAD\nAUNTS\nLINGO\nSTABS\n";
+
+fn board(text: &str) -> SharedBoard {
+    text.parse().unwrap()
+}
+
+#[test]
+fn boards_are_ranked_by_day_and_week() {
+    let (puzzle, dictionary) = gbm();
+    let dir = scratch_path("team-ranks");
+    let team = Leaderboard::new(&dir);
+    let submit = |player, date, text| {
+        team.submit(player, date, &puzzle, &board(text), &dictionary)
+            .unwrap()
+    };
+    assert_eq!(submit("alice", "2026-10-13", BEST).score, 1003);
+    submit("bob", "2026-10-13", SECOND);
+    submit("alice", "2026-10-14", SECOND);
+    submit("carol", "2026-10-14", SECOND);
+    // Resubmitting replaces the day's board
+    submit("dan", "2026-10-14", SECOND);
+    submit("dan", "2026-10-14", BEST);
+    // The next Monday starts a new week
+    submit("bob", "2026-10-19", BEST);
+    team.set_optimum("2026-10-14", 1003).unwrap();
+
+    let rank = |rank, player: &str, score, gap| DailyRank {
+        rank,
+        player: player.to_string(),
+        score,
+        gap,
+    };
+    assert_eq!(
+        team.daily("2026-10-14").unwrap(),
+        [
+            rank(1, "dan", 1003, Some(0)),
+            rank(2, "alice", 996, Some(7)),
+            rank(2, "carol", 996, Some(7)),
+        ]
+    );
+    assert_eq!(
+        team.daily("2026-10-13").unwrap(),
+        [rank(1, "alice", 1003, None), rank(2, "bob", 996, None)]
+    );
+    assert!(team.daily("2026-10-15").unwrap().is_empty());
+
+    let total = |rank, player: &str, total, days| WeeklyTotal {
+        rank,
+        player: player.to_string(),
+        total,
+        days,
+    };
+    assert_eq!(
+        team.weekly("2026-10-18").unwrap(),
+        [
+            total(1, "alice", 1999, 2),
+            total(2, "dan", 1003, 1),
+            total(3, "bob", 996, 1),
+            total(3, "carol", 996, 1),
+        ]
+    );
+    assert_eq!(
+        Leaderboard::week_of("2026-10-18").unwrap(),
+        [
+            "2026-10-12",
+            "2026-10-13",
+            "2026-10-14",
+            "2026-10-15",
+            "2026-10-16",
+            "2026-10-17",
+            "2026-10-18"
+        ]
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn submissions_are_checked() {
+    let (puzzle, dictionary) = gbm();
+    let dir = scratch_path("team-checks");
+    let team = Leaderboard::new(&dir);
+    let err = |player, date, puzzle: &Puzzle, text| {
+        team.submit(player, date, puzzle, &board(text), &dictionary)
+            .unwrap_err()
+            .to_string()
+    };
+
+    // A bad board doesn't fix the day's puzzle
+    let e = err(
+        "alice",
+        "2026-10-18",
+        &puzzle,
+        "BATON\nBROAD\nLUMEN\nSTABS\nFUZZY\n",
+    );
+    assert!(e.contains("more"), "{e}");
+    assert_eq!(team.day_puzzle("2026-10-18").unwrap(), None);
+
+    for player in ["", "../alice", ".hidden", "a b", "puzzle"] {
+        err(player, "2026-10-18", &puzzle, BEST);
+    }
+    let e = err("alice", "18/10/2026", &puzzle, BEST);
+    assert!(e.contains("bad date"), "{e}");
+
+    // Once a day has a puzzle, boards for another one are turned away
+    team.submit("alice", "2026-10-18", &puzzle, &board(BEST), &dictionary)
+        .unwrap();
+    let other: Puzzle = fixture("bongo-bhm.puzzle").parse().unwrap();
+    let e = err("bob", "2026-10-18", &other, BEST);
+    assert!(e.contains("is being played with puzzle"), "{e}");
+    team.submit("bob", "2026-10-18", &puzzle, &board(BEST), &dictionary)
+        .unwrap();
+    assert_eq!(team.submissions("2026-10-18").unwrap().len(), 2);
+
+    // Dates name the same day however they're written
+    let early = team
+        .submit("carol", "2026-1-5", &puzzle, &board(BEST), &dictionary)
+        .unwrap();
+    assert_eq!(early.date, "2026-01-05");
+    assert_eq!(team.submissions("2026-01-05").unwrap(), [early]);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,236 @@
+//! Property tests for the bag accounting and scoring invariants the search
+//! relies on.
+
+use bongo_game::packed::{PackedWord, WILDCARD};
+use bongo_game::puzzle::{Letter, LetterBag, Multiplier, Puzzle, RoundingMode, RoundingScope};
+use bongo_game::solver::{
+    Bonus, add_word_to_bag, prescore_word_in_row, remove_word_from_bag, score_board, score_word,
+};
+use proptest::prelude::*;
+
+/// A small alphabet keeps words, bags and wildcards colliding often.
+const ALPHABET: &[u8] = b"ABCDEF";
+
+fn arb_word() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(ALPHABET), 5)
+        .prop_map(|letters| String::from_utf8(letters).unwrap())
+}
+
+/// A word, plus which of its letters (if any) is marked as coming from the wildcard.
+fn arb_word_with_wildcard() -> impl Strategy<Value = (String, Option<char>)> {
+    (arb_word(), any::<Option<prop::sample::Index>>()).prop_map(|(word, wildcard)| {
+        let wildcard = wildcard.map(|i| word.as_bytes()[i.index(word.len())] as char);
+        (word, wildcard)
+    })
+}
+
+fn arb_bag() -> impl Strategy<Value = LetterBag> {
+    (prop::collection::vec(0u8..4, ALPHABET.len()), 0u8..3).prop_map(|(counts, wildcards)| {
+        let mut bag = [0; WILDCARD + 1];
+        for (&ch, count) in ALPHABET.iter().zip(counts) {
+            bag[(ch - b'A') as usize] = count;
+        }
+        bag[WILDCARD] = wildcards;
+        bag
+    })
+}
+
+fn arb_rounding() -> impl Strategy<Value = (RoundingMode, RoundingScope)> {
+    (
+        prop::sample::select(vec![
+            RoundingMode::Floor,
+            RoundingMode::Ceil,
+            RoundingMode::Nearest,
+        ]),
+        prop::sample::select(vec![RoundingScope::PerWord, RoundingScope::PerBoard]),
+    )
+}
+
+fn arb_puzzle() -> impl Strategy<Value = Puzzle> {
+    (
+        prop::collection::vec(0u32..60, ALPHABET.len()),
+        prop::array::uniform5(prop::array::uniform5(1u32..4)),
+        prop::array::uniform4(0usize..5),
+        (10u32..20, 1u32..11),
+        arb_rounding(),
+    )
+        .prop_map(
+            |(scores, schema, bonus_cols, (num, den), (rounding, scope))| {
+                let mut letters = ALPHABET
+                    .iter()
+                    .zip(scores)
+                    .map(|(&ch, score)| Letter {
+                        ch: ch as char,
+                        num: 5,
+                        score,
+                    })
+                    .collect::<Vec<_>>();
+                letters.push(Letter {
+                    ch: '*',
+                    num: 1,
+                    score: 0,
+                });
+                Puzzle {
+                    letters,
+                    schema,
+                    bonus_path: [0, 1, 2, 3].map(|r| (r, bonus_cols[r])),
+                    common_multiplier: Multiplier { num, den },
+                    rounding,
+                    rounding_scope: scope,
+                    ..Puzzle::default()
+                }
+            },
+        )
+}
+
+/// Five rows, with the wildcard standing in for a letter of one of them.
+fn arb_board() -> impl Strategy<Value = (Vec<String>, usize, usize)> {
+    (
+        prop::collection::vec(arb_word(), 5),
+        0usize..5,
+        any::<prop::sample::Index>(),
+    )
+        .prop_map(|(rows, wild_row, wild_col)| {
+            let wild_col = wild_col.index(5);
+            (rows, wild_row, wild_col)
+        })
+}
+
+fn pack_board(
+    rows: &[String],
+    wild_row: usize,
+    wild_col: usize,
+    puzzle: &Puzzle,
+) -> Vec<PackedWord> {
+    rows.iter()
+        .enumerate()
+        .map(|(r, word)| {
+            let wildcard = (r == wild_row).then(|| word.as_bytes()[wild_col] as char);
+            PackedWord::new(word, wildcard, puzzle)
+        })
+        .collect()
+}
+
+fn with_star_at(word: &str, col: usize) -> String {
+    let mut word = word.as_bytes().to_vec();
+    word[col] = b'*';
+    String::from_utf8(word).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn removing_a_word_never_overdraws_the_bag(
+        (word, wildcard) in arb_word_with_wildcard(),
+        bag in arb_bag(),
+    ) {
+        let word = PackedWord::new(&word, wildcard, &Puzzle::default());
+        if let Some(after) = remove_word_from_bag(&word, &bag) {
+            for (a, b) in after.iter().zip(&bag) {
+                prop_assert!(a <= b);
+            }
+            let used: u32 = bag.iter().zip(&after).map(|(&b, &a)| (b - a) as u32).sum();
+            prop_assert_eq!(used, word.len as u32);
+            if wildcard.is_none() {
+                prop_assert_eq!(after[WILDCARD], bag[WILDCARD]);
+            }
+        }
+    }
+
+    #[test]
+    fn removing_then_adding_restores_the_bag(
+        (word, wildcard) in arb_word_with_wildcard(),
+        bag in arb_bag(),
+    ) {
+        let word = PackedWord::new(&word, wildcard, &Puzzle::default());
+        if let Some(after) = remove_word_from_bag(&word, &bag) {
+            prop_assert_eq!(add_word_to_bag(&word, &after, &bag), bag);
+        }
+    }
+
+    #[test]
+    fn wildcard_cell_scores_zero(
+        word in arb_word(),
+        row in 0usize..5,
+        col in 0usize..5,
+        puzzle in arb_puzzle(),
+    ) {
+        let packed = PackedWord::new(&word, None, &puzzle);
+        let starred = PackedWord::new(&with_star_at(&word, col), None, &puzzle);
+        prop_assert_eq!(
+            score_word(row, &packed, Some((row, col)), &puzzle),
+            score_word(row, &starred, None, &puzzle)
+        );
+        // A wildcard in another row doesn't touch this one
+        let other_row = (row + 1) % 5;
+        prop_assert_eq!(
+            score_word(row, &packed, Some((other_row, col)), &puzzle),
+            score_word(row, &packed, None, &puzzle)
+        );
+    }
+
+    #[test]
+    fn board_score_is_best_wildcard_placement(
+        (rows, wild_row, wild_col) in arb_board(),
+        puzzle in arb_puzzle(),
+    ) {
+        let packed = pack_board(&rows, wild_row, wild_col, &puzzle);
+        let board = packed.iter().collect::<Vec<_>>();
+        let wild_letter = rows[wild_row].as_bytes()[wild_col];
+
+        // The wildcard can stand in for the first copy of its letter in any row
+        let best_placement = rows
+            .iter()
+            .enumerate()
+            .filter_map(|(r, word)| word.bytes().position(|l| l == wild_letter).map(|c| (r, c)))
+            .map(|(r, c)| {
+                let mut starred_rows = rows.clone();
+                starred_rows[r] = with_star_at(&rows[r], c);
+                let packed = pack_board(&starred_rows, 5, 0, &puzzle);
+                score_board(&packed.iter().collect::<Vec<_>>(), Bonus::Spelled { common: true }, &puzzle)
+            })
+            .max()
+            .unwrap();
+        prop_assert_eq!(score_board(&board, Bonus::Spelled { common: true }, &puzzle), best_placement);
+    }
+
+    #[test]
+    fn score_is_monotone_in_multipliers(
+        (rows, wild_row, wild_col) in arb_board(),
+        puzzle in arb_puzzle(),
+        (bump_row, bump_col, bump) in (0usize..5, 0usize..5, 0u32..3),
+        extra_num in 0u32..5,
+    ) {
+        let mut bumped = puzzle.clone();
+        bumped.schema[bump_row][bump_col] += bump;
+        bumped.common_multiplier.num += extra_num;
+
+        let before = pack_board(&rows, wild_row, wild_col, &puzzle);
+        let after = pack_board(&rows, wild_row, wild_col, &bumped);
+        for (r, (b, a)) in before.iter().zip(&after).enumerate() {
+            prop_assert!(score_word(r, a, None, &bumped) >= score_word(r, b, None, &puzzle));
+        }
+        for bonus in [Bonus::Missed, Bonus::Spelled { common: false }, Bonus::Spelled { common: true }] {
+            prop_assert!(
+                score_board(&after.iter().collect::<Vec<_>>(), bonus, &bumped)
+                    >= score_board(&before.iter().collect::<Vec<_>>(), bonus, &puzzle)
+            );
+        }
+    }
+
+    #[test]
+    fn prescore_bounds_the_row_score(
+        (rows, wild_row, wild_col) in arb_board(),
+        puzzle in arb_puzzle(),
+    ) {
+        let packed = pack_board(&rows, wild_row, wild_col, &puzzle);
+        // Wherever the board ends up putting the wildcard, no row scores more
+        // than its prescore
+        for (row, word) in packed.iter().enumerate() {
+            let prescore = prescore_word_in_row(row, word, &puzzle);
+            prop_assert!(score_word(row, word, None, &puzzle) <= prescore);
+            for (r, c) in (0..5).flat_map(|r| (0..5).map(move |c| (r, c))) {
+                prop_assert!(score_word(row, word, Some((r, c)), &puzzle) <= prescore);
+            }
+        }
+    }
+}
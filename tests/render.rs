@@ -0,0 +1,54 @@
+mod common;
+
+use bongo_game::render::{board_svg, escape};
+use bongo_game::solver::{Bonus, solve, split_valid_words};
+use common::load;
+
+#[test]
+fn boards_are_drawn_like_the_game() {
+    let (puzzle, valid_words) = load("bongo-gbm.puzzle", "bongo-gbm-words.txt");
+    let (bonus_words, rows) = split_valid_words(&valid_words, &puzzle);
+    let (board, _) = solve(&rows, &bonus_words, &puzzle, |_| {})
+        .into_iter()
+        .max_by_key(|(_, score)| *score)
+        .unwrap();
+    let svg = board_svg(&board, Bonus::Spelled { common: true }, &puzzle);
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches(r#"rx="6""#).count(), 25, "one tile per cell");
+    assert_eq!(svg.matches(r##"fill="#f6d365""##).count(), 4, "bonus path");
+    // Multipliers show on the bonus path too: (3, 3) is tripled
+    assert_eq!(svg.matches(">×2<").count(), 2);
+    assert_eq!(svg.matches(">×3<").count(), 1);
+    assert_eq!(svg.matches("stroke-dasharray").count(), 1, "one wildcard");
+    for word in ["BATON", "BROAD", "LUMEN", "STABS", "FRAGS"] {
+        for letter in word.chars() {
+            assert!(svg.contains(&format!(">{letter}</text>")));
+        }
+    }
+    // Letter scores, with the wildcard worth nothing
+    assert!(svg.contains(">50</text>"));
+    assert!(svg.contains(">0</text>"));
+    assert!(svg.contains(">Bonus TOMB +263</text>"));
+    assert!(svg.contains(">Total 1003</text>"));
+}
+
+#[test]
+fn empty_boards_show_the_layout() {
+    let (puzzle, _) = load("bongo-gbm.puzzle", "bongo-gbm-words.txt");
+    let svg = board_svg(&[], Bonus::Missed, &puzzle);
+    assert_eq!(svg.matches(r#"rx="6""#).count(), 25);
+    assert!(!svg.contains("Total"));
+    assert!(!svg.contains("font-size=\"32\""));
+}
+
+#[test]
+fn titles_are_escaped() {
+    let (mut puzzle, _) = load("bongo-gbm.puzzle", "bongo-gbm-words.txt");
+    puzzle.id = Some("<Tom & Jerry's>".to_string());
+    assert!(
+        board_svg(&[], Bonus::Missed, &puzzle).contains(">&lt;Tom &amp; Jerry&#39;s&gt;</text>")
+    );
+    assert_eq!(escape("\"a\""), "&quot;a&quot;");
+}
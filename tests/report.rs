@@ -0,0 +1,95 @@
+mod common;
+
+use bongo_game::puzzle::Puzzle;
+use bongo_game::report::SolveReport;
+use common::{fixture, with_gbm_report};
+use serde_json::Value;
+
+fn gbm_report(top: usize) -> SolveReport {
+    with_gbm_report("gbm", top, 0, |report, _, _, _| report)
+}
+
+#[test]
+fn board_reports_break_the_total_down() {
+    let report = gbm_report(3);
+    assert_eq!(report.boards.len(), 3);
+    let best = &report.boards[0];
+    assert_eq!(best.rank, 1);
+    assert_eq!(best.total, 1003);
+    assert_eq!(
+        best.rows
+            .iter()
+            .map(|r| r.word.as_str())
+            .collect::<Vec<_>>(),
+        ["BATON", "BROAD", "LUMEN", "STABS", "FRAGS"]
+    );
+    assert_eq!(best.bonus_word, "TOMB");
+    let wildcard = best.wildcard.as_ref().unwrap();
+    assert_eq!((wildcard.row, wildcard.col, wildcard.letter), (4, 0, 'F'));
+    for board in &report.boards {
+        let rows: u32 = board.rows.iter().map(|r| r.score).sum();
+        assert_eq!(rows + board.bonus_score.unwrap_or(0), board.total);
+    }
+    assert!(report.boards.windows(2).all(|w| w[0].total >= w[1].total));
+}
+
+#[test]
+fn json_holds_the_puzzle_stats_and_boards() {
+    let mut out = vec![];
+    gbm_report(2).write_json(&mut out).unwrap();
+    let json: Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(json["puzzle_id"], "gbm");
+    assert_eq!(json["stats"]["strategy"], "bonus-first");
+    assert_eq!(json["boards"].as_array().unwrap().len(), 2);
+    assert_eq!(json["boards"][0]["total"], 1003);
+    assert_eq!(json["boards"][0]["rows"][3]["word"], "STABS");
+    assert_eq!(json["boards"][0]["wildcard"]["letter"], "F");
+    assert_eq!(json["boards"][1]["rank"], 2);
+}
+
+#[test]
+fn json_lines_has_one_self_contained_board_per_line() {
+    let mut out = vec![];
+    gbm_report(3).write_json_lines(&mut out).unwrap();
+    let lines = String::from_utf8(out).unwrap();
+    let boards = lines
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(boards.len(), 3);
+    for (i, board) in boards.iter().enumerate() {
+        assert_eq!(board["puzzle_id"], "gbm");
+        assert_eq!(board["rank"], i + 1);
+        assert_eq!(board["stats"]["bonus_candidates"], 216);
+    }
+    assert_eq!(boards[0]["bonus_word"], "TOMB");
+}
+
+#[test]
+fn csv_has_a_row_per_board() {
+    let mut out = vec![];
+    gbm_report(3).write_csv(&mut out).unwrap();
+    let mut reader = csv::Reader::from_reader(out.as_slice());
+    let header = reader.headers().unwrap().clone();
+    let column = |name: &str| header.iter().position(|h| h == name).unwrap();
+    let records = reader.records().map(Result::unwrap).collect::<Vec<_>>();
+    assert_eq!(records.len(), 3);
+    let best = &records[0];
+    assert_eq!(&best[column("puzzle_id")], "gbm");
+    assert_eq!(&best[column("total")], "1003");
+    assert_eq!(&best[column("row1")], "BATON");
+    assert_eq!(&best[column("row5_score")], "140");
+    assert_eq!(&best[column("bonus_word")], "TOMB");
+    assert_eq!(&best[column("wildcard_letter")], "F");
+    assert_eq!(&records[2][column("rank")], "3");
+}
+
+#[test]
+fn puzzle_ids_round_trip() {
+    let text = format!("id = gbm-2024-05-01\n{}", fixture("bongo-gbm.puzzle"));
+    let puzzle: Puzzle = text.parse().unwrap();
+    assert_eq!(puzzle.id.as_deref(), Some("gbm-2024-05-01"));
+    let reparsed: Puzzle = puzzle.to_string().parse().unwrap();
+    assert_eq!(reparsed.id, puzzle.id);
+    assert_eq!(Puzzle::default().id, None);
+}
@@ -0,0 +1,197 @@
+mod common;
+
+use bongo_game::packed::PackedWord;
+use bongo_game::puzzle::{Multiplier, Puzzle, RoundingMode, RoundingScope};
+use bongo_game::scoring::{BongoRules, ScoringRules};
+use bongo_game::solver::{
+    Bonus, Strategy, prescore_word_in_row, score_board, score_word, solve_dictionary,
+    solve_word_list,
+};
+use common::{gbm, load};
+use std::sync::Arc;
+
+/// A house variant: the wildcard is worth 40, words are doubled, and the bonus
+/// word doesn't score. Everything else is the game's.
+#[derive(Debug)]
+struct HouseRules;
+
+impl ScoringRules for HouseRules {
+    fn letter_value(&self, puzzle: &Puzzle, letter: char) -> u32 {
+        if letter == '*' {
+            return self.wildcard_value(puzzle);
+        }
+        BongoRules.letter_value(puzzle, letter)
+    }
+
+    fn cell_multiplier(&self, puzzle: &Puzzle, row: usize, col: usize) -> u32 {
+        BongoRules.cell_multiplier(puzzle, row, col)
+    }
+
+    fn wildcard_value(&self, _: &Puzzle) -> u32 {
+        40
+    }
+
+    fn word_multiplier(&self, _: &Puzzle, _: bool) -> Multiplier {
+        Multiplier { num: 2, den: 1 }
+    }
+
+    fn bonus_multiplier(&self, _: &Puzzle, _: bool) -> Option<Multiplier> {
+        None
+    }
+
+    fn rounding(&self, _: &Puzzle) -> (RoundingMode, RoundingScope) {
+        (RoundingMode::Floor, RoundingScope::PerWord)
+    }
+}
+
+/// A variant that halves uncommon words instead of just leaving out the bonus.
+#[derive(Debug)]
+struct HalveUncommon;
+
+impl ScoringRules for HalveUncommon {
+    fn letter_value(&self, puzzle: &Puzzle, letter: char) -> u32 {
+        BongoRules.letter_value(puzzle, letter)
+    }
+
+    fn cell_multiplier(&self, puzzle: &Puzzle, row: usize, col: usize) -> u32 {
+        BongoRules.cell_multiplier(puzzle, row, col)
+    }
+
+    fn wildcard_value(&self, puzzle: &Puzzle) -> u32 {
+        BongoRules.wildcard_value(puzzle)
+    }
+
+    fn word_multiplier(&self, puzzle: &Puzzle, common: bool) -> Multiplier {
+        if common {
+            BongoRules.word_multiplier(puzzle, true)
+        } else {
+            Multiplier { num: 1, den: 2 }
+        }
+    }
+
+    fn bonus_multiplier(&self, puzzle: &Puzzle, common: bool) -> Option<Multiplier> {
+        Some(self.word_multiplier(puzzle, common))
+    }
+
+    fn rounding(&self, puzzle: &Puzzle) -> (RoundingMode, RoundingScope) {
+        BongoRules.rounding(puzzle)
+    }
+}
+
+fn house_puzzle() -> Puzzle {
+    Puzzle {
+        rules: Arc::new(HouseRules),
+        ..Puzzle::default()
+    }
+}
+
+fn pack(rows: [&str; 5], wildcard_row: usize, puzzle: &Puzzle) -> Vec<PackedWord> {
+    rows.iter()
+        .enumerate()
+        .map(|(r, word)| {
+            let wildcard = (r == wildcard_row).then(|| word.chars().next().unwrap());
+            PackedWord::new(word, wildcard, puzzle)
+        })
+        .collect()
+}
+
+#[test]
+fn default_rules_score_like_the_game() {
+    let puzzle = Puzzle::default();
+    // B(50) + O(7) + N(20) + G(45) + O(7) on the plain first row, x1.3 rounded up
+    let word = PackedWord::new("BONGO", None, &puzzle);
+    assert_eq!(score_word(0, &word, None, &puzzle), 168);
+    // The wildcard standing in for the B scores nothing
+    assert_eq!(score_word(0, &word, Some((0, 0)), &puzzle), 103);
+}
+
+#[test]
+fn only_common_words_get_the_multiplier() {
+    let puzzle = Puzzle::default();
+    let mut word = PackedWord::new("BONGO", None, &puzzle);
+    word.common = false;
+    assert_eq!(score_word(0, &word, None, &puzzle), 129);
+    assert_eq!(prescore_word_in_row(0, &word, &puzzle), 129);
+
+    // N(20) three times down the middle, then G(45) on a x3 cell: 195, or 254
+    // once multiplied
+    let rows = pack(["BONGO"; 5], 5, &puzzle);
+    let board = rows.iter().collect::<Vec<_>>();
+    let missed = score_board(&board, Bonus::Missed, &puzzle);
+    let uncommon = score_board(&board, Bonus::Spelled { common: false }, &puzzle);
+    let common = score_board(&board, Bonus::Spelled { common: true }, &puzzle);
+    assert_eq!(uncommon, missed + 195);
+    assert_eq!(common, missed + 254);
+}
+
+#[test]
+fn uncommon_dictionary_words_lose_the_multiplier() {
+    let (puzzle, mut dictionary) = gbm();
+    // BATON is the first row of the best board, worth 120 as a common word
+    dictionary.insert("BATON", false);
+    let (board, score) = solve_dictionary(&dictionary, &puzzle, Strategy::BonusFirst, |_| {});
+    assert!(score < 1003, "{score}");
+    let words = board.iter().map(|w| w.word.as_str()).collect::<Vec<_>>();
+    assert_eq!(words, ["EMBER", "BROAD", "AUNTS", "LINGO", "STABS"]);
+}
+
+#[test]
+fn house_rules_change_word_and_wildcard_scores() {
+    let puzzle = house_puzzle();
+    let word = PackedWord::new("BONGO", None, &puzzle);
+    assert_eq!(score_word(0, &word, None, &puzzle), 2 * 129);
+    // The wildcard is worth 40 in place of the O's 7
+    assert_eq!(
+        score_word(0, &word, Some((0, 1)), &puzzle),
+        2 * (129 - 7 + 40)
+    );
+    // A word with a cheap letter can gain from the wildcard, and the prescore
+    // has to allow for it
+    assert_eq!(prescore_word_in_row(0, &word, &puzzle), 2 * (129 - 7 + 40));
+}
+
+#[test]
+fn rules_can_treat_uncommon_words_their_own_way() {
+    let puzzle = Puzzle {
+        rules: Arc::new(HalveUncommon),
+        ..Puzzle::default()
+    };
+    let mut word = PackedWord::new("BONGO", None, &puzzle);
+    assert_eq!(score_word(0, &word, None, &puzzle), 168);
+    word.common = false;
+    // 129 halved, rounded up
+    assert_eq!(score_word(0, &word, None, &puzzle), 65);
+}
+
+#[test]
+fn house_rules_ignore_the_bonus_word() {
+    let puzzle = house_puzzle();
+    let rows = pack(["BONGO", "BONGO", "BONGO", "BONGO", "BONGO"], 5, &puzzle);
+    let board = rows.iter().collect::<Vec<_>>();
+    assert_eq!(
+        score_board(&board, Bonus::Spelled { common: true }, &puzzle),
+        score_board(&board, Bonus::Missed, &puzzle)
+    );
+
+    let puzzle = Puzzle::default();
+    let rows = pack(["BONGO", "BONGO", "BONGO", "BONGO", "BONGO"], 5, &puzzle);
+    let board = rows.iter().collect::<Vec<_>>();
+    let spelled = score_board(&board, Bonus::Spelled { common: true }, &puzzle);
+    assert!(spelled > score_board(&board, Bonus::Missed, &puzzle));
+}
+
+#[test]
+fn solver_follows_the_puzzle_rules() {
+    let (puzzle, words) = load("bongo-gbm.puzzle", "bongo-gbm-words.txt");
+    let words = words.into_iter().map(|w| w.word).collect::<Vec<_>>();
+    let (_, game_score) = solve_word_list(&words, &puzzle, Strategy::BonusFirst, |_| {});
+    assert_eq!(game_score, 1003);
+
+    let puzzle = Puzzle {
+        rules: Arc::new(HouseRules),
+        ..puzzle
+    };
+    let (board, house_score) = solve_word_list(&words, &puzzle, Strategy::BonusFirst, |_| {});
+    assert_eq!(board.len(), 5);
+    assert_ne!(house_score, game_score);
+}
@@ -0,0 +1,190 @@
+mod common;
+
+use bongo_game::Error;
+use bongo_game::share::SharedBoard;
+use common::gbm;
+
+const SHARED: &str = "Bongo 2024-05-01 Score: 1,003
+🟦🟦🟨🟦🟦
+BONGO
+B A T O N
+|b|r|o|a|d|
+L U M E N
+S,T,A,B,S
+F*R A G S
+Play at bongo.example
+";
+
+#[test]
+fn share_text_is_parsed() {
+    let shared: SharedBoard = SHARED.parse().unwrap();
+    assert_eq!(shared.rows, ["BATON", "BROAD", "LUMEN", "STABS", "FRAGS"]);
+    assert_eq!(shared.wildcard, Some((4, 0)));
+    assert_eq!(shared.claimed_score, Some(1003));
+
+    let plain: SharedBoard = "baton\nbroad\nlumen\nstabs\nfrags\n980 points\n"
+        .parse()
+        .unwrap();
+    assert_eq!(plain.rows, shared.rows);
+    assert_eq!(plain.wildcard, None);
+    assert_eq!(plain.claimed_score, Some(980));
+}
+
+#[test]
+fn text_without_a_grid_is_rejected() {
+    for text in [
+        "",
+        "BATON\nBROAD\nLUMEN\nSTABS\n",
+        "BATON\nBROAD\n\nLUMEN\nSTABS\nFRAGS\n",
+        "BATONS\nBROAD\nLUMEN\nSTABS\nFRAGS\n",
+    ] {
+        assert!(text.parse::<SharedBoard>().is_err(), "{text:?}");
+    }
+    let err = "B*ATON\nBROAD\nLUMEN\nSTABS\nF*RAGS\n"
+        .parse::<SharedBoard>()
+        .unwrap_err();
+    assert!(err.to_string().contains("more than one cell"), "{err}");
+}
+
+#[test]
+fn extra_rows_next_to_the_grid_are_not_guessed_at() {
+    // A word right under the grid would shift it down a row
+    let text = "BATON\nBROAD\nLUMEN\nSTABS\nFRAGS\nTOTAL\n1003 points\n";
+    let err = text.parse::<SharedBoard>().unwrap_err().to_string();
+    assert!(err.contains("6 rows of five letters in a row"), "{err}");
+
+    // The wildcard mark says where the grid is
+    let marked = text.replacen("BATON", "B*ATON", 1);
+    let shared: SharedBoard = marked.parse().unwrap();
+    assert_eq!(shared.rows, ["BATON", "BROAD", "LUMEN", "STABS", "FRAGS"]);
+    assert_eq!(shared.wildcard, Some((0, 0)));
+    // Unless it's in a row both readings share
+    let err = text
+        .replacen("LUMEN", "L*UMEN", 1)
+        .parse::<SharedBoard>()
+        .unwrap_err();
+    assert!(err.to_string().contains("6 rows"), "{err}");
+    assert_eq!(shared.claimed_score, Some(1003));
+
+    // So does a blank line
+    let separated = text.replacen("FRAGS\n", "FRAGS\n\n", 1);
+    let shared: SharedBoard = separated.parse().unwrap();
+    assert_eq!(shared.rows, ["BATON", "BROAD", "LUMEN", "STABS", "FRAGS"]);
+}
+
+#[test]
+fn shared_boards_score_like_the_solver() {
+    let (puzzle, dictionary) = gbm();
+    let shared: SharedBoard = SHARED.parse().unwrap();
+    let checked = shared.check(&puzzle, &dictionary).unwrap();
+    assert_eq!(checked.score, 1003);
+    assert_eq!(checked.bonus_word, "TOMB");
+    assert!(checked.bonus_word_used);
+    assert_eq!(checked.board().len(), 5);
+
+    // Without the mark the wildcard is found from the letter that runs out
+    let unmarked: SharedBoard = SHARED.replace('*', "").parse().unwrap();
+    assert_eq!(unmarked.check(&puzzle, &dictionary).unwrap().score, 1003);
+}
+
+#[test]
+fn the_marked_wildcard_is_scored_where_it_was_put() {
+    let (puzzle, mut dictionary) = gbm();
+    // A fifth A, so the wildcard stands in for one of them
+    let rows = ["AAABB", "BMDNN", "ULTTO", "ORRAS", "SSEEA"];
+    for row in rows {
+        dictionary.insert(row, true);
+    }
+    let check = |text: String| {
+        text.parse::<SharedBoard>()
+            .unwrap()
+            .check(&puzzle, &dictionary)
+            .unwrap()
+    };
+    let unmarked = check(rows.join("\n"));
+    let mut marked_rows = rows.map(String::from);
+    marked_rows[3] = "ORRA*S".to_string();
+    let marked = check(marked_rows.join("\n"));
+    // Unmarked, it goes in a single letter score cell; marked, it stays on
+    // the triple
+    assert_ne!(unmarked.wildcard, Some((3, 3)));
+    assert_eq!(marked.wildcard, Some((3, 3)));
+    assert!(marked.score < unmarked.score);
+    let summary = marked.summary(&puzzle, 0);
+    let row = summary.lines().nth(4).unwrap();
+    let raw: u32 = 7 + 7 * 2 + 7 + 5;
+    assert!(
+        row.ends_with(&format!(" {}", (raw * 13).div_ceil(10))),
+        "{summary}"
+    );
+}
+
+#[test]
+fn boards_that_dont_fit_the_puzzle_are_rejected() {
+    let (puzzle, dictionary) = gbm();
+    let check = |text: &str| {
+        text.parse::<SharedBoard>()
+            .unwrap()
+            .check(&puzzle, &dictionary)
+            .unwrap_err()
+            .to_string()
+    };
+    // Two letters beyond the bag, with only one wildcard
+    let err = check("BATON\nBROAD\nLUMEN\nSTABS\nFUZZY\n");
+    assert!(err.contains("more"), "{err}");
+    // The marked wildcard is spent, so the extra F has no tile
+    let err = check("BATON\nB*ROAD\nLUMEN\nSTABS\nFRAGS\n");
+    assert!(err.contains("more F tiles"), "{err}");
+    let err = check("BATON\nBROAD\nLUMEN\nSTABS\nGRABS\n");
+    assert!(
+        err.contains("row 5 `GRABS` isn't in the dictionary"),
+        "{err}"
+    );
+    let err = check("BATON\nBROAD\nLUMEN\nSTABS\nFRAGÜ\n");
+    assert!(err.contains("'Ü' in row 5"), "{err}");
+
+    // Boards that break the rules aren't usage mistakes
+    let err = "BATON\nBROAD\nLUMEN\nSTABS\nGRABS\n"
+        .parse::<SharedBoard>()
+        .unwrap()
+        .check(&puzzle, &dictionary)
+        .unwrap_err();
+    assert!(matches!(err, Error::Invalid(_)), "{err:?}");
+}
+
+#[test]
+fn summaries_give_nothing_away() {
+    let (mut puzzle, mut dictionary) = gbm();
+    puzzle.id = Some("gbm".to_string());
+    let shared: SharedBoard = SHARED.parse().unwrap();
+    let summary = shared
+        .check(&puzzle, &dictionary)
+        .unwrap()
+        .summary(&puzzle, 1003);
+    assert_eq!(
+        summary,
+        "Bongo gbm · 1003/1003 (100%)
+🟩🟩🟨🟩🟩 120
+🟩🟩🟨🟩🟩 129
+🟩🟩🟨🟩🟩 110
+🟩🟦🟩🟨🟩 241
+🟩🟩🟩🟦🟩 140
+Bonus word ✅ · 5/5 common words
+"
+    );
+    for word in &shared.rows {
+        assert!(!summary.contains(word.as_str()));
+    }
+
+    // An uncommon row and a bonus path that isn't a word
+    dictionary.insert("AUNTS", false);
+    let shared: SharedBoard = "EMBER\nBROAD\nAUNTS\nSTABS\nLINGO\n".parse().unwrap();
+    let checked = shared.check(&puzzle, &dictionary).unwrap();
+    assert!(!checked.bonus_word_used);
+    let summary = checked.summary(&puzzle, 1003);
+    let lines = summary.lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with(&format!("Bongo gbm · {}/1003 (", checked.score)));
+    assert!(lines[1].starts_with("🟩🟩⬛🟩🟩"));
+    assert!(lines[3].starts_with("⬜⬜⬛⬜⬜"));
+    assert_eq!(lines[6], "Bonus word ❌ · 4/5 common words");
+}